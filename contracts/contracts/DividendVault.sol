@@ -5,6 +5,8 @@ import "@openzeppelin/contracts/token/ERC20/IERC20.sol";
 import "@openzeppelin/contracts/token/ERC20/utils/SafeERC20.sol";
 import "@openzeppelin/contracts/access/Ownable.sol";
 import "@openzeppelin/contracts/utils/cryptography/MerkleProof.sol";
+import "@openzeppelin/contracts/utils/cryptography/ECDSA.sol";
+import "@openzeppelin/contracts/utils/cryptography/MessageHashUtils.sol";
 
 /**
  * @title DividendVault - Distributes licensing revenue to RoyaltyToken holders.
@@ -17,6 +19,11 @@ import "@openzeppelin/contracts/utils/cryptography/MerkleProof.sol";
  *      4. Token holders claim their dividends by providing a Merkle proof.
  *
  *      This is the "Code is Law" dividend distribution — trustless and verifiable.
+ *
+ *      Small distributions can skip the tree: the owner funds a voucher epoch and the
+ *      Vault signs one EIP-712 ClaimVoucher per claim, which anyone may redeem for its
+ *      recipient until its deadline. The domain is the Vault's signing domain
+ *      (name, version, chainId), shared with its distribution attestations.
  */
 contract DividendVault is Ownable {
     using SafeERC20 for IERC20;
@@ -36,8 +43,30 @@ contract DividendVault is Ownable {
     /// @notice Tracks claimed status: epoch => address => claimed.
     mapping(uint256 => mapping(address => bool)) public claimed;
 
+    /// @notice Key whose vouchers may be redeemed (the Vault's signing key).
+    address public voucherSigner;
+
+    /// @notice Epochs funded for vouchers rather than a Merkle root.
+    mapping(uint256 => bool) public voucherEpochs;
+
+    /// @notice USDC paid out by vouchers per epoch.
+    mapping(uint256 => uint256) public epochRedeemed;
+
+    /// @notice Redeemed voucher nonces.
+    mapping(uint256 => bool) public voucherRedeemed;
+
+    bytes32 private constant DOMAIN_TYPEHASH =
+        keccak256("EIP712Domain(string name,string version,uint256 chainId)");
+    bytes32 public constant VOUCHER_TYPEHASH = keccak256(
+        "ClaimVoucher(address recipient,uint256 amount,uint256 nonce,uint256 deadline,uint256 epoch,string distributionId)"
+    );
+    string public constant DOMAIN_NAME = "IdeaCapital Vault";
+    string public constant DOMAIN_VERSION = "1";
+
     event NewDistribution(uint256 indexed epoch, bytes32 merkleRoot, uint256 totalAmount);
     event DividendClaimed(uint256 indexed epoch, address indexed claimant, uint256 amount);
+    event VoucherSignerUpdated(address indexed signer);
+    event VoucherRedeemed(uint256 indexed epoch, address indexed recipient, uint256 nonce, uint256 amount, address signer);
 
     constructor(address _usdc) Ownable(msg.sender) {
         usdc = IERC20(_usdc);
@@ -96,6 +125,78 @@ contract DividendVault is Ownable {
         emit DividendClaimed(epoch, msg.sender, amount);
     }
 
+    /**
+     * @notice Set the key whose vouchers may be redeemed.
+     */
+    function setVoucherSigner(address signer) external onlyOwner {
+        require(signer != address(0), "Invalid signer");
+        voucherSigner = signer;
+        emit VoucherSignerUpdated(signer);
+    }
+
+    /**
+     * @notice Fund a new epoch paid out with vouchers instead of a Merkle root.
+     * @dev Emits NewDistribution with a zero root, so claimDividend never pays from it.
+     * @param totalAmount The total USDC the epoch's vouchers may pay.
+     */
+    function createVoucherDistribution(uint256 totalAmount) external onlyOwner {
+        require(totalAmount > 0, "Zero amount");
+
+        usdc.safeTransferFrom(msg.sender, address(this), totalAmount);
+
+        currentEpoch++;
+        voucherEpochs[currentEpoch] = true;
+        epochTotals[currentEpoch] = totalAmount;
+
+        emit NewDistribution(currentEpoch, bytes32(0), totalAmount);
+    }
+
+    /**
+     * @notice Redeem a voucher signed by the voucher signer, paying its recipient.
+     * @param recipient The wallet the voucher pays.
+     * @param amount The USDC amount.
+     * @param nonce Unique per voucher; each nonce is paid once.
+     * @param deadline Last timestamp the voucher can be redeemed at.
+     * @param epoch The voucher epoch that funds it.
+     * @param distributionId The Vault's distribution ID.
+     * @param signature The 65-byte signature over the voucher's EIP-712 hash.
+     */
+    function redeemVoucher(
+        address recipient,
+        uint256 amount,
+        uint256 nonce,
+        uint256 deadline,
+        uint256 epoch,
+        string calldata distributionId,
+        bytes calldata signature
+    ) external {
+        require(voucherEpochs[epoch], "Invalid epoch");
+        require(block.timestamp <= deadline, "Voucher expired");
+        require(!voucherRedeemed[nonce], "Already redeemed");
+        require(epochRedeemed[epoch] + amount <= epochTotals[epoch], "Epoch exhausted");
+
+        bytes32 structHash = keccak256(
+            abi.encode(VOUCHER_TYPEHASH, recipient, amount, nonce, deadline, epoch, keccak256(bytes(distributionId)))
+        );
+        address signer = ECDSA.recover(MessageHashUtils.toTypedDataHash(domainSeparator(), structHash), signature);
+        require(signer == voucherSigner, "Invalid signature");
+
+        voucherRedeemed[nonce] = true;
+        epochRedeemed[epoch] += amount;
+        usdc.safeTransfer(recipient, amount);
+
+        emit VoucherRedeemed(epoch, recipient, nonce, amount, signer);
+    }
+
+    /**
+     * @notice The EIP-712 domain separator of the Vault's signatures on this chain.
+     */
+    function domainSeparator() public view returns (bytes32) {
+        return keccak256(
+            abi.encode(DOMAIN_TYPEHASH, keccak256(bytes(DOMAIN_NAME)), keccak256(bytes(DOMAIN_VERSION)), block.chainid)
+        );
+    }
+
     /**
      * @notice Check if an address has claimed for a specific epoch.
      */
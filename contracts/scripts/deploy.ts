@@ -36,6 +36,15 @@ async function main() {
     const vault = await DividendVault.deploy(USDC_ADDRESS);
     await vault.waitForDeployment();
     console.log("DividendVault deployed to:", await vault.getAddress());
+
+    // Vouchers are signed by the Vault's attestation key
+    const VOUCHER_SIGNER = process.env.VOUCHER_SIGNER_ADDRESS;
+    if (VOUCHER_SIGNER) {
+      await (await vault.setVoucherSigner(VOUCHER_SIGNER)).wait();
+      console.log("Voucher signer set to:", VOUCHER_SIGNER);
+    } else {
+      console.log("WARNING: VOUCHER_SIGNER_ADDRESS not set; vouchers cannot be redeemed until setVoucherSigner is called");
+    }
   }
 
   console.log("\n--- Deployment Complete ---");
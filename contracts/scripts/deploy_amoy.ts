@@ -22,6 +22,10 @@ async function main() {
   const vault = await DividendVault.deploy(USDC_ADDRESS);
   await vault.waitForDeployment();
   console.log("✅ DividendVault deployed to:", await vault.getAddress());
+  if (process.env.VOUCHER_SIGNER_ADDRESS) {
+    await (await vault.setVoucherSigner(process.env.VOUCHER_SIGNER_ADDRESS)).wait();
+    console.log("✅ Voucher signer set to:", process.env.VOUCHER_SIGNER_ADDRESS);
+  }
 
   // 4. Deploy StoryProtocolAdapter
   // Mock registry address for now
@@ -198,4 +198,123 @@ describe("DividendVault", function () {
       expect(vaultBalance).to.equal(0);
     });
   });
+
+  describe("Voucher Redemption", function () {
+    const voucherTypes = {
+      ClaimVoucher: [
+        { name: "recipient", type: "address" },
+        { name: "amount", type: "uint256" },
+        { name: "nonce", type: "uint256" },
+        { name: "deadline", type: "uint256" },
+        { name: "epoch", type: "uint256" },
+        { name: "distributionId", type: "string" },
+      ],
+    };
+    const amount = ethers.parseUnits("250", 6);
+    const totalAmount = ethers.parseUnits("400", 6);
+    const distributionId = "7d0d8a6e-8a7e-4a44-9f8e-3b0f3c1d2e4f";
+    let signer: any;
+    let deadline: bigint;
+
+    async function signVoucher(overrides: any = {}, key: any = signer) {
+      const { chainId } = await ethers.provider.getNetwork();
+      const voucher = {
+        recipient: claimant1.address,
+        amount,
+        nonce: 42n,
+        deadline,
+        epoch: 1n,
+        distributionId,
+        ...overrides,
+      };
+      const signature = await key.signTypedData(
+        { name: "IdeaCapital Vault", version: "1", chainId },
+        voucherTypes,
+        voucher
+      );
+      return { voucher, signature };
+    }
+
+    function redeem({ voucher, signature }: any, from: any = claimant2) {
+      return vault
+        .connect(from)
+        .redeemVoucher(
+          voucher.recipient,
+          voucher.amount,
+          voucher.nonce,
+          voucher.deadline,
+          voucher.epoch,
+          voucher.distributionId,
+          signature
+        );
+    }
+
+    beforeEach(async function () {
+      signer = claimant3;
+      await vault.setVoucherSigner(signer.address);
+
+      const block = await ethers.provider.getBlock("latest");
+      deadline = BigInt(block!.timestamp + 3600);
+
+      await mockUSDC.approve(await vault.getAddress(), totalAmount);
+      await expect(vault.createVoucherDistribution(totalAmount))
+        .to.emit(vault, "NewDistribution")
+        .withArgs(1, ethers.ZeroHash, totalAmount);
+    });
+
+    it("should pay the recipient of a signed voucher", async function () {
+      const balanceBefore = await mockUSDC.balanceOf(claimant1.address);
+
+      // Anyone may submit a voucher; the recipient is paid
+      await expect(redeem(await signVoucher()))
+        .to.emit(vault, "VoucherRedeemed")
+        .withArgs(1, claimant1.address, 42, amount, signer.address);
+
+      expect(await mockUSDC.balanceOf(claimant1.address) - balanceBefore).to.equal(amount);
+      expect(await vault.voucherRedeemed(42)).to.equal(true);
+      expect(await vault.epochRedeemed(1)).to.equal(amount);
+    });
+
+    it("should redeem each nonce once", async function () {
+      const signed = await signVoucher();
+      await redeem(signed);
+      await expect(redeem(signed)).to.be.revertedWith("Already redeemed");
+    });
+
+    it("should reject vouchers from another signer or with altered terms", async function () {
+      await expect(redeem(await signVoucher({}, owner))).to.be.revertedWith("Invalid signature");
+
+      const signed = await signVoucher();
+      signed.voucher.recipient = claimant2.address;
+      await expect(redeem(signed)).to.be.revertedWith("Invalid signature");
+    });
+
+    it("should reject expired vouchers and non-voucher epochs", async function () {
+      const block = await ethers.provider.getBlock("latest");
+      await expect(redeem(await signVoucher({ deadline: BigInt(block!.timestamp) - 1n })))
+        .to.be.revertedWith("Voucher expired");
+
+      const merkleRoot = ethers.keccak256(ethers.toUtf8Bytes("root"));
+      await mockUSDC.approve(await vault.getAddress(), amount);
+      await vault.createDistribution(merkleRoot, amount);
+      await expect(redeem(await signVoucher({ epoch: 2n }))).to.be.revertedWith("Invalid epoch");
+    });
+
+    it("should not pay more than the epoch holds", async function () {
+      await redeem(await signVoucher());
+      await expect(redeem(await signVoucher({ nonce: 43n }))).to.be.revertedWith("Epoch exhausted");
+    });
+
+    it("should never pay voucher epochs through claimDividend", async function () {
+      await expect(vault.connect(claimant1).claimDividend(1, amount, []))
+        .to.be.revertedWith("Invalid epoch");
+    });
+
+    it("should only let the owner set the signer or fund voucher epochs", async function () {
+      await expect(vault.connect(claimant1).setVoucherSigner(claimant1.address))
+        .to.be.revertedWithCustomError(vault, "OwnableUnauthorizedAccount");
+      await expect(vault.connect(claimant1).createVoucherDistribution(amount))
+        .to.be.revertedWithCustomError(vault, "OwnableUnauthorizedAccount");
+    });
+  });
 });
@@ -0,0 +1,90 @@
+//! Merkle Builder Benchmark
+//!
+//! Builds a tree for 1M synthetic claims streamed from an iterator and checks that
+//! peak heap usage stays within a fixed budget. Run with:
+//! `cargo bench --bench merkle_build` (set `MERKLE_BENCH_LEAVES` to change the size).
+
+#[allow(dead_code)]
+#[path = "../src/crypto/merkle.rs"]
+mod merkle;
+
+use merkle::{ClaimLeaf, MerkleTreeBuilder};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Heap budget per leaf: 32-byte leaf hash while streaming, then `2n - 1` nodes,
+/// the sort order and leaf indices, plus slack for batch buffers.
+const BYTES_PER_LEAF_BUDGET: usize = 112;
+/// Fixed overhead for the claim batch buffer and thread bookkeeping.
+const FIXED_BUDGET: usize = 16 * 1024 * 1024;
+
+/// Global allocator that tracks current and peak heap usage.
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let leaves: usize = std::env::var("MERKLE_BENCH_LEAVES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1_000_000);
+
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let started = Instant::now();
+
+    // Claims are generated lazily, the way rows arrive from a database cursor
+    let claims = (1..=leaves).map(|i| ClaimLeaf {
+        wallet_address: format!("0x{:040x}", i),
+        amount_wei: (i as u64 * 1_000).to_string(),
+    });
+
+    let mut builder = MerkleTreeBuilder::default();
+    builder.extend(claims).expect("valid claims");
+    let hashed = started.elapsed();
+
+    let tree = builder.finish().expect("tree builds");
+    let total = started.elapsed();
+
+    let peak = PEAK.load(Ordering::Relaxed) - baseline;
+    let budget = leaves * BYTES_PER_LEAF_BUDGET + FIXED_BUDGET;
+
+    println!("leaves:        {}", tree.len());
+    println!("root:          0x{}", hex::encode(tree.root()));
+    println!("leaf hashing:  {:?}", hashed);
+    println!("total build:   {:?}", total);
+    println!(
+        "peak heap:     {:.1} MiB (budget {:.1} MiB, {:.1} bytes/leaf)",
+        peak as f64 / 1048576.0,
+        budget as f64 / 1048576.0,
+        peak as f64 / leaves as f64
+    );
+
+    assert!(
+        peak <= budget,
+        "peak heap {} bytes exceeds budget {} bytes",
+        peak,
+        budget
+    );
+}
@@ -0,0 +1,17 @@
+-- Persisted Merkle Trees
+-- Stores each distribution's tree once; claim proofs are derived on request
+-- instead of being written as a TEXT[] per claim.
+
+CREATE TABLE dividend_merkle_trees (
+    distribution_id UUID PRIMARY KEY REFERENCES dividend_distributions(id),
+    leaf_count INT NOT NULL,
+    nodes BYTEA NOT NULL, -- 32-byte nodes concatenated in StandardMerkleTree array order (root first)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Node hashes do not compress; EXTERNAL storage keeps substring() reads of single nodes cheap
+ALTER TABLE dividend_merkle_trees ALTER COLUMN nodes SET STORAGE EXTERNAL;
+
+-- Leaf position in dividend_merkle_trees.nodes. NULL for older claims that carry a stored merkle_proof.
+ALTER TABLE dividend_claims
+ADD COLUMN tree_index INT;
@@ -0,0 +1,7 @@
+-- Merkle Leaf Schemas
+-- Records how each distribution's leaves were encoded, e.g.
+-- {"type": "address_amount"} or {"type": "epoch_address_amount", "epoch": 3}.
+-- Proofs can only be verified or rebuilt with the schema the tree was built with.
+
+ALTER TABLE dividend_distributions
+ADD COLUMN leaf_schema JSONB NOT NULL DEFAULT '{"type": "address_amount"}';
@@ -0,0 +1,36 @@
+-- Cumulative Distributions
+-- In CUMULATIVE mode each leaf is a wallet's lifetime entitlement for the invention,
+-- so a distributor contract pays (leaf amount - claimed so far) in one transaction.
+
+ALTER TABLE dividend_distributions
+ADD COLUMN mode TEXT NOT NULL DEFAULT 'PER_EPOCH' CHECK (mode IN ('PER_EPOCH', 'CUMULATIVE'));
+
+-- amount_usdc stays the amount added by this distribution; the leaf commits to the running total
+ALTER TABLE dividend_claims
+ADD COLUMN cumulative_amount_usdc NUMERIC(18, 6);
+
+-- Cumulative distributor contract holding the invention's current root
+ALTER TABLE invention_ledger
+ADD COLUMN cumulative_distributor_address TEXT;
+
+-- Claimed-to-date per wallet, maintained from the distributor's Claimed events
+CREATE TABLE dividend_claimed_to_date (
+    invention_id TEXT NOT NULL REFERENCES invention_ledger(invention_id),
+    wallet_address TEXT NOT NULL, -- lowercase
+    claimed_usdc NUMERIC(18, 6) NOT NULL DEFAULT 0,
+    last_claim_tx_hash TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (invention_id, wallet_address)
+);
+
+-- Every indexed Claimed event, so replayed logs are not counted twice
+CREATE TABLE cumulative_claim_events (
+    tx_hash TEXT NOT NULL,
+    log_index BIGINT NOT NULL,
+    invention_id TEXT NOT NULL REFERENCES invention_ledger(invention_id),
+    wallet_address TEXT NOT NULL,
+    amount_usdc NUMERIC(18, 6) NOT NULL,
+    block_number BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tx_hash, log_index)
+);
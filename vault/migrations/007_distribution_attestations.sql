@@ -0,0 +1,8 @@
+-- Distribution Attestations
+-- EIP-712 signature by the Vault's attestation key over each distribution it produced
+-- (inventionId, distributionId, merkleRoot, totalAmount, claimCount, chainId).
+
+ALTER TABLE dividend_distributions
+ADD COLUMN attestation_signer TEXT,
+ADD COLUMN attestation_signature TEXT,
+ADD COLUMN attestation_chain_id BIGINT;
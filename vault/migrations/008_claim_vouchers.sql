@@ -0,0 +1,14 @@
+-- Voucher Distributions
+-- Small distributions can pay out through EIP-712 signed vouchers instead of a Merkle tree.
+-- Voucher distributions have no Merkle root.
+
+ALTER TABLE dividend_distributions
+ADD COLUMN payout_method TEXT NOT NULL DEFAULT 'MERKLE' CHECK (payout_method IN ('MERKLE', 'VOUCHER'));
+
+ALTER TABLE dividend_distributions
+ALTER COLUMN merkle_root DROP NOT NULL;
+
+-- Signed voucher (message, signer, signature) for voucher claims; NULL for Merkle claims
+ALTER TABLE dividend_claims
+ADD COLUMN voucher JSONB,
+ADD COLUMN claimed_at TIMESTAMPTZ;
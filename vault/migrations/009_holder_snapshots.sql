@@ -0,0 +1,6 @@
+-- Holder Snapshots
+-- Block at which holder balances were read from the invention's RoyaltyToken.
+-- NULL when the caller supplied the balances (inventions without a token).
+
+ALTER TABLE dividend_distributions
+ADD COLUMN snapshot_block BIGINT;
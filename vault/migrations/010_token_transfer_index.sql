@@ -0,0 +1,41 @@
+-- RoyaltyToken Transfer Index
+-- ERC-20 Transfer events for every invention_ledger.royalty_token_address, folded into
+-- per-wallet balance history so balances at any indexed block come from Postgres
+-- instead of an archive node. Token and wallet addresses are lowercase; amounts are base units.
+
+CREATE TABLE token_transfers (
+    token_address TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    from_address TEXT NOT NULL,
+    to_address TEXT NOT NULL,
+    value NUMERIC(78, 0) NOT NULL,
+    PRIMARY KEY (token_address, block_number, log_index)
+);
+
+-- Wallet balance at the end of each block in which it changed
+CREATE TABLE token_balance_history (
+    token_address TEXT NOT NULL,
+    wallet_address TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    balance NUMERIC(78, 0) NOT NULL,
+    PRIMARY KEY (token_address, wallet_address, block_number)
+);
+
+CREATE INDEX idx_token_balance_history_block ON token_balance_history(token_address, block_number);
+
+-- Number of non-zero balances at the end of each block with transfers
+CREATE TABLE token_holder_counts (
+    token_address TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    holder_count BIGINT NOT NULL,
+    PRIMARY KEY (token_address, block_number)
+);
+
+-- Last block whose transfers are fully reflected in the tables above
+CREATE TABLE token_index_checkpoints (
+    token_address TEXT PRIMARY KEY,
+    last_indexed_block BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
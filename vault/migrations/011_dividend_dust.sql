@@ -0,0 +1,8 @@
+-- Dividend Dust
+-- Base units left over after flooring every holder's share, and the holders that
+-- received one unit each (largest fractional remainders). Leaf amounts always sum
+-- exactly to the deposit.
+
+ALTER TABLE dividend_distributions
+ADD COLUMN dust_usdc NUMERIC(18, 6) NOT NULL DEFAULT 0,
+ADD COLUMN dust_recipients JSONB NOT NULL DEFAULT '[]';
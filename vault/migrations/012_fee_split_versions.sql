@@ -0,0 +1,12 @@
+-- Fee Split Versions
+-- Every change to an invention's compliance_fee_splits produces a new version holding
+-- the complete split set after the change. Audit logs carry the before/after snapshots.
+
+CREATE TABLE compliance_fee_split_versions (
+    invention_id TEXT NOT NULL REFERENCES invention_ledger(invention_id),
+    version INT NOT NULL,
+    splits JSONB NOT NULL, -- Full split set after the change
+    actor TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (invention_id, version)
+);
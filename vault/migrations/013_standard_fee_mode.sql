@@ -0,0 +1,13 @@
+-- Standard Fee Mode
+-- Inventions without ABS fee sharing (fee_sharing_enabled = FALSE) pay a platform fee
+-- and an inventor share out of revenue; the rest goes to the investor pool.
+
+ALTER TABLE invention_ledger
+ADD COLUMN platform_fee_percentage NUMERIC(5, 2), -- Percentage of revenue (0-100)
+ADD COLUMN platform_fee_address TEXT,
+ADD COLUMN inventor_share_percentage NUMERIC(5, 2), -- Percentage of revenue (0-100)
+ADD COLUMN inventor_wallet_address TEXT;
+
+-- Fee mode and per-recipient amounts applied by each distribution
+ALTER TABLE dividend_distributions
+ADD COLUMN revenue_breakdown JSONB;
@@ -0,0 +1,14 @@
+-- Distribution Lifecycle
+-- DRAFT -> APPROVED -> PUBLISHED -> CLOSED, with VETOED (compliance lawyer) and
+-- CANCELLED as exits before publication. Claims are only offered while PUBLISHED.
+-- Every transition is recorded in audit_logs as DISTRIBUTION_STATUS_CHANGE.
+
+-- Existing distributions were final on creation, so they start out published
+ALTER TABLE dividend_distributions
+ADD COLUMN status TEXT NOT NULL DEFAULT 'PUBLISHED'
+    CHECK (status IN ('DRAFT', 'APPROVED', 'PUBLISHED', 'CLOSED', 'VETOED', 'CANCELLED')),
+ADD COLUMN status_updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE dividend_distributions ALTER COLUMN status SET DEFAULT 'DRAFT';
+
+CREATE INDEX idx_distributions_invention_status ON dividend_distributions(invention_id, status);
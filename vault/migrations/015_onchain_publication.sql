@@ -0,0 +1,15 @@
+-- On-chain Publication
+-- Merkle distributions are published by calling DividendVault.createDistribution.
+-- The transaction hash is stored with the distribution, so retries follow it instead
+-- of funding the epoch twice (since 023, the transaction is signed and stored before it
+-- is broadcast); the epoch comes from the NewDistribution event.
+
+ALTER TABLE dividend_distributions
+ADD COLUMN dividend_vault_address TEXT,
+ADD COLUMN publish_tx_hash TEXT,
+ADD COLUMN epoch BIGINT;
+
+-- An epoch belongs to exactly one distribution per DividendVault
+CREATE UNIQUE INDEX idx_distributions_vault_epoch
+    ON dividend_distributions(dividend_vault_address, epoch)
+    WHERE epoch IS NOT NULL;
@@ -0,0 +1,28 @@
+-- DividendVault Claim Index
+-- DividendClaimed(epoch, claimant, amount) events from the DividendVault, matched to
+-- claim rows by epoch and wallet to keep dividend_claims.claimed in sync.
+
+CREATE TABLE dividend_claim_events (
+    tx_hash TEXT NOT NULL,
+    log_index BIGINT NOT NULL,
+    dividend_vault_address TEXT NOT NULL, -- lowercase
+    epoch BIGINT NOT NULL,
+    wallet_address TEXT NOT NULL, -- lowercase
+    amount_usdc NUMERIC(18, 6) NOT NULL,
+    block_number BIGINT NOT NULL,
+    claim_id UUID REFERENCES dividend_claims(id),
+    -- MATCHED, AMOUNT_MISMATCH (paid amount differs from the claim), UNMATCHED (no claim row)
+    match_status TEXT NOT NULL CHECK (match_status IN ('MATCHED', 'AMOUNT_MISMATCH', 'UNMATCHED')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tx_hash, log_index)
+);
+
+CREATE INDEX idx_dividend_claim_events_status ON dividend_claim_events(match_status)
+    WHERE match_status <> 'MATCHED';
+
+-- Last block whose DividendClaimed events are fully reflected above
+CREATE TABLE dividend_vault_checkpoints (
+    dividend_vault_address TEXT PRIMARY KEY,
+    last_indexed_block BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
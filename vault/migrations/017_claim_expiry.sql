@@ -0,0 +1,42 @@
+-- Unclaimed Dividend Expiry
+-- Each invention may set a claim window. Once it has passed since publication, the
+-- distribution is closed and its unclaimed amounts are recorded in unclaimed_dividends
+-- under the invention's policy:
+--   ROLLOVER: added to the investor pool of the invention's next distribution
+--   TREASURY: paid to dao_treasury_address as a leaf of the next distribution
+--   ESCHEAT:  held and reported for unclaimed property filings
+
+ALTER TABLE invention_ledger
+ADD COLUMN claim_window_days INTEGER CHECK (claim_window_days > 0), -- NULL: claims never expire
+ADD COLUMN unclaimed_policy TEXT NOT NULL DEFAULT 'ESCHEAT'
+    CHECK (unclaimed_policy IN ('ROLLOVER', 'TREASURY', 'ESCHEAT')),
+ADD COLUMN dao_treasury_address TEXT,
+ADD CONSTRAINT invention_ledger_treasury_policy
+    CHECK (unclaimed_policy <> 'TREASURY' OR dao_treasury_address IS NOT NULL);
+
+-- Claim windows run from publication
+ALTER TABLE dividend_distributions
+ADD COLUMN published_at TIMESTAMPTZ;
+
+UPDATE dividend_distributions SET published_at = created_at WHERE status IN ('PUBLISHED', 'CLOSED');
+
+ALTER TABLE dividend_claims
+ADD COLUMN expired_at TIMESTAMPTZ;
+
+CREATE TABLE unclaimed_dividends (
+    claim_id UUID PRIMARY KEY REFERENCES dividend_claims(id),
+    distribution_id UUID NOT NULL REFERENCES dividend_distributions(id),
+    invention_id TEXT NOT NULL REFERENCES invention_ledger(invention_id),
+    wallet_address TEXT NOT NULL,
+    amount_usdc NUMERIC(18, 6) NOT NULL,
+    policy TEXT NOT NULL CHECK (policy IN ('ROLLOVER', 'TREASURY', 'ESCHEAT')),
+    treasury_address TEXT, -- TREASURY only
+    expired_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Distribution the amount was rolled into or paid to the treasury by
+    applied_distribution_id UUID REFERENCES dividend_distributions(id),
+    -- DividendVault has no expiry, so a holder can still claim on-chain afterwards
+    claimed_after_expiry_tx_hash TEXT
+);
+
+CREATE INDEX idx_unclaimed_dividends_pending ON unclaimed_dividends(invention_id)
+    WHERE applied_distribution_id IS NULL;
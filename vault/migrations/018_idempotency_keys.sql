@@ -0,0 +1,14 @@
+-- Idempotency Keys
+-- Clients may send an Idempotency-Key header with distribute and verify requests.
+-- The first request with a key stores a fingerprint of its body and, once it
+-- succeeds, its response; retries with the same key get the stored response back.
+
+CREATE TABLE idempotency_keys (
+    scope TEXT NOT NULL,                -- endpoint the key was used with
+    key TEXT NOT NULL,
+    request_hash TEXT NOT NULL,         -- SHA-256 of the request, hex
+    response JSONB,                     -- NULL while the first request is running
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+    PRIMARY KEY (scope, key)
+);
@@ -0,0 +1,29 @@
+-- Minimum Payout
+-- Each invention may set a minimum payout. In per-epoch distributions, a wallet whose
+-- amount (plus its carry-forward balance) is below it gets no leaf; the amount is
+-- credited to its carry-forward balance instead and paid with a later distribution
+-- once the balance reaches the minimum.
+
+ALTER TABLE invention_ledger
+ADD COLUMN min_payout_usdc NUMERIC(18, 6) CHECK (min_payout_usdc > 0); -- NULL: every amount is paid
+
+-- One entry per wallet a distribution credits (positive) or pays out (negative)
+CREATE TABLE dividend_carry_forward (
+    distribution_id UUID NOT NULL REFERENCES dividend_distributions(id),
+    invention_id TEXT NOT NULL,
+    wallet_address TEXT NOT NULL, -- lowercase
+    amount_usdc NUMERIC(18, 6) NOT NULL CHECK (amount_usdc <> 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (distribution_id, wallet_address)
+);
+
+CREATE INDEX idx_dividend_carry_forward_invention ON dividend_carry_forward(invention_id, wallet_address);
+
+-- Entries of vetoed and cancelled distributions never took effect
+CREATE VIEW dividend_carry_forward_balances AS
+SELECT f.invention_id, f.wallet_address, SUM(f.amount_usdc) AS balance_usdc
+FROM dividend_carry_forward f
+JOIN dividend_distributions d ON d.id = f.distribution_id
+WHERE d.status NOT IN ('VETOED', 'CANCELLED')
+GROUP BY f.invention_id, f.wallet_address
+HAVING SUM(f.amount_usdc) <> 0;
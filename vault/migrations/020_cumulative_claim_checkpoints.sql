@@ -0,0 +1,9 @@
+-- Cumulative Claim Checkpoints
+-- Last block whose Claimed events are fully reflected in cumulative_claim_events, per
+-- distributor, so claims made while the watcher was down are indexed on restart.
+
+CREATE TABLE cumulative_distributor_checkpoints (
+    distributor_address TEXT PRIMARY KEY, -- lowercase
+    last_indexed_block BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- Fee Split Duplicate Types
+-- A split may share its recipient type with other splits of the invention once it has
+-- been created or updated with this allowance. Stored on the row, so later edits of
+-- either split do not have to repeat it.
+
+ALTER TABLE compliance_fee_splits
+ADD COLUMN allow_duplicate_type BOOLEAN NOT NULL DEFAULT false;
+
+-- Duplicates that exist already were allowed when they were created
+UPDATE compliance_fee_splits s
+SET allow_duplicate_type = true
+WHERE EXISTS (
+    SELECT 1 FROM compliance_fee_splits o
+    WHERE o.invention_id = s.invention_id AND o.recipient_type = s.recipient_type AND o.id <> s.id
+);
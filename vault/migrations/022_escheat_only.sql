@@ -0,0 +1,19 @@
+-- Escheat-Only Unclaimed Policy
+-- DividendVault has no expiry or sweep, so the USDC of an expired claim stays in the
+-- contract and can still be claimed. Rolling it over or paying it to the treasury would
+-- fund it a second time. Until the contract can sweep expired epochs, expired claims
+-- are only recorded for reporting.
+
+UPDATE invention_ledger SET unclaimed_policy = 'ESCHEAT' WHERE unclaimed_policy <> 'ESCHEAT';
+
+-- Entries no live distribution has carried are kept in the ledger instead
+UPDATE unclaimed_dividends u
+SET policy = 'ESCHEAT', treasury_address = NULL, applied_distribution_id = NULL
+WHERE u.policy <> 'ESCHEAT'
+  AND NOT EXISTS (
+      SELECT 1 FROM dividend_distributions d
+      WHERE d.id = u.applied_distribution_id AND d.status NOT IN ('VETOED', 'CANCELLED')
+  );
+
+ALTER TABLE invention_ledger
+ADD CONSTRAINT invention_ledger_escheat_only CHECK (unclaimed_policy = 'ESCHEAT');
@@ -0,0 +1,14 @@
+-- Publishing Claim
+-- A publisher moves an approved distribution to PUBLISHING before it touches the chain,
+-- so no row lock is held while transactions confirm. The createDistribution transaction
+-- is signed locally and stored with its nonce before it is broadcast. A retry rebroadcasts
+-- the stored transaction instead of signing and funding a second one.
+
+ALTER TABLE dividend_distributions DROP CONSTRAINT dividend_distributions_status_check;
+ALTER TABLE dividend_distributions
+ADD CONSTRAINT dividend_distributions_status_check
+    CHECK (status IN ('DRAFT', 'APPROVED', 'PUBLISHING', 'PUBLISHED', 'CLOSED', 'VETOED', 'CANCELLED'));
+
+ALTER TABLE dividend_distributions
+ADD COLUMN publish_nonce BIGINT,
+ADD COLUMN publish_raw_tx BYTEA; -- signed createDistribution, for rebroadcasting
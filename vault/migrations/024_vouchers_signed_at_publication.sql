@@ -0,0 +1,22 @@
+-- Vouchers Signed at Publication
+-- Voucher distributions are signed when they are published, not when they are created,
+-- so no voucher exists for a draft, approved, vetoed or cancelled distribution.
+-- The requested expiry is kept on the distribution until then.
+
+ALTER TABLE dividend_distributions
+ADD COLUMN voucher_deadline BIGINT; -- Unix timestamp; NULL: 90 days after publication
+
+-- Keep the expiry of vouchers signed at creation by unpublished distributions, then drop them
+UPDATE dividend_distributions d
+SET voucher_deadline = (
+    SELECT MAX((c.voucher->'message'->>'deadline')::BIGINT)
+    FROM dividend_claims c WHERE c.distribution_id = d.id
+)
+WHERE d.payout_method = 'VOUCHER';
+
+UPDATE dividend_claims c
+SET voucher = NULL
+FROM dividend_distributions d
+WHERE d.id = c.distribution_id
+  AND d.payout_method = 'VOUCHER'
+  AND d.status NOT IN ('PUBLISHED', 'CLOSED');
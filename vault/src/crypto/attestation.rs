@@ -0,0 +1,209 @@
+//! EIP-712 Distribution Attestations
+//!
+//! The Vault signs a typed-data statement for every distribution root it produces, so
+//! compliance can show which roots came from the Vault independently of whoever later
+//! posts them on-chain. The payload is standard `eth_signTypedData_v4` data and can be
+//! checked with any EIP-712 tooling (e.g. `ethers.verifyTypedData`) or the Vault's
+//! `/dividends/verify-attestation` endpoint.
+//!
+//! ```text
+//! DistributionAttestation(string inventionId,string distributionId,bytes32 merkleRoot,
+//!                         uint256 totalAmount,uint256 claimCount,uint256 chainId)
+//! ```
+
+use anyhow::{anyhow, Result};
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::transaction::eip712::{Eip712, TypedData};
+use ethers::types::{Address, Signature, H256};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::crypto::merkle::parse_node;
+
+/// EIP-712 domain name; the domain has no verifying contract.
+pub const DOMAIN_NAME: &str = "IdeaCapital Vault";
+pub const DOMAIN_VERSION: &str = "1";
+pub const PRIMARY_TYPE: &str = "DistributionAttestation";
+
+/// The attested facts about one distribution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributionAttestation {
+    pub invention_id: String,
+    pub distribution_id: String,
+    pub merkle_root: String,
+    /// Total distributed, in USDC base units (6 decimals), as a decimal string.
+    pub total_amount: String,
+    pub claim_count: u64,
+    pub chain_id: u64,
+}
+
+/// An attestation with the Vault's signature over its EIP-712 hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedAttestation {
+    pub message: DistributionAttestation,
+    pub signer: String,
+    pub signature: String,
+}
+
+impl DistributionAttestation {
+    /// Full typed-data payload, as passed to `eth_signTypedData_v4`.
+    pub fn typed_data(&self) -> Result<TypedData> {
+        // ethers pads short byte strings, so check the root is a full bytes32 first
+        parse_node(&self.merkle_root)?;
+
+        let typed_data = serde_json::json!({
+            "types": {
+                "EIP712Domain": domain_type(),
+                PRIMARY_TYPE: [
+                    { "name": "inventionId", "type": "string" },
+                    { "name": "distributionId", "type": "string" },
+                    { "name": "merkleRoot", "type": "bytes32" },
+                    { "name": "totalAmount", "type": "uint256" },
+                    { "name": "claimCount", "type": "uint256" },
+                    { "name": "chainId", "type": "uint256" }
+                ]
+            },
+            "primaryType": PRIMARY_TYPE,
+            "domain": domain(self.chain_id),
+            "message": {
+                "inventionId": self.invention_id,
+                "distributionId": self.distribution_id,
+                "merkleRoot": self.merkle_root,
+                "totalAmount": self.total_amount,
+                "claimCount": self.claim_count,
+                "chainId": self.chain_id
+            }
+        });
+
+        serde_json::from_value(typed_data).map_err(|e| anyhow!("Invalid attestation: {}", e))
+    }
+
+    /// EIP-712 digest: `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+    pub fn signing_hash(&self) -> Result<[u8; 32]> {
+        self.typed_data()?
+            .encode_eip712()
+            .map_err(|e| anyhow!("Failed to hash attestation: {}", e))
+    }
+
+    /// Sign with `wallet`, returning a 65-byte `r ‖ s ‖ v` signature.
+    pub fn sign(&self, wallet: &LocalWallet) -> Result<SignedAttestation> {
+        let signature = wallet
+            .sign_hash(H256::from(self.signing_hash()?))
+            .map_err(|e| anyhow!("Failed to sign attestation: {}", e))?;
+
+        Ok(SignedAttestation {
+            message: self.clone(),
+            signer: format!("{:#x}", wallet.address()),
+            signature: format!("0x{}", signature),
+        })
+    }
+
+    /// Recover the address that produced `signature` over this attestation.
+    pub fn recover(&self, signature: &str) -> Result<Address> {
+        let signature = Signature::from_str(signature)
+            .map_err(|e| anyhow!("Invalid signature '{}': {}", signature, e))?;
+
+        signature
+            .recover(H256::from(self.signing_hash()?))
+            .map_err(|e| anyhow!("Failed to recover signer: {}", e))
+    }
+}
+
+/// `EIP712Domain` fields shared by all Vault-signed typed data.
+pub(crate) fn domain_type() -> serde_json::Value {
+    serde_json::json!([
+        { "name": "name", "type": "string" },
+        { "name": "version", "type": "string" },
+        { "name": "chainId", "type": "uint256" }
+    ])
+}
+
+/// The Vault's EIP-712 domain on `chain_id`.
+pub(crate) fn domain(chain_id: u64) -> serde_json::Value {
+    serde_json::json!({
+        "name": DOMAIN_NAME,
+        "version": DOMAIN_VERSION,
+        "chainId": chain_id
+    })
+}
+
+/// Vault signing key and chain, from `VAULT_ATTESTATION_PRIVATE_KEY` and `CHAIN_ID`.
+///
+/// The same key signs distribution attestations and claim vouchers.
+/// Returns `None` when no key is configured (local development).
+pub fn signer_from_env() -> Result<Option<(LocalWallet, u64)>> {
+    let Ok(key) = std::env::var("VAULT_ATTESTATION_PRIVATE_KEY") else {
+        return Ok(None);
+    };
+
+    let wallet = LocalWallet::from_str(&key)
+        .map_err(|e| anyhow!("Invalid VAULT_ATTESTATION_PRIVATE_KEY: {}", e))?;
+    let chain_id: u64 = std::env::var("CHAIN_ID")
+        .map_err(|_| anyhow!("CHAIN_ID must be set to sign attestations"))?
+        .parse()
+        .map_err(|e| anyhow!("Invalid CHAIN_ID: {}", e))?;
+
+    Ok(Some((wallet, chain_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hardhat account #0; never use outside tests
+    const TEST_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn sample() -> DistributionAttestation {
+        DistributionAttestation {
+            invention_id: "inv-1".to_string(),
+            distribution_id: "7d0d8a6e-8a7e-4a44-9f8e-3b0f3c1d2e4f".to_string(),
+            merkle_root: "0x8140f9815bda3adf6750884e0c94c193c9be3e5891d90d97d4e73934124ec5b1".to_string(),
+            total_amount: "6000000".to_string(),
+            claim_count: 2,
+            chain_id: 31337,
+        }
+    }
+
+    #[test]
+    fn test_sign_and_recover() {
+        let wallet = LocalWallet::from_str(TEST_KEY).unwrap();
+        let signed = sample().sign(&wallet).unwrap();
+
+        assert_eq!(signed.signer, "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+        assert_eq!(signed.signature.len(), 2 + 65 * 2);
+        assert_eq!(sample().recover(&signed.signature).unwrap(), wallet.address());
+    }
+
+    #[test]
+    fn test_changed_field_recovers_different_signer() {
+        let wallet = LocalWallet::from_str(TEST_KEY).unwrap();
+        let signed = sample().sign(&wallet).unwrap();
+
+        let mut tampered = sample();
+        tampered.merkle_root = format!("0x{}", "11".repeat(32));
+        assert_ne!(tampered.recover(&signed.signature).unwrap(), wallet.address());
+
+        let mut other_chain = sample();
+        other_chain.chain_id = 8453;
+        assert_ne!(other_chain.recover(&signed.signature).unwrap(), wallet.address());
+    }
+
+    #[test]
+    fn test_typed_data_round_trips_through_json() {
+        let typed_data = sample().typed_data().unwrap();
+        let json = serde_json::to_string(&typed_data).unwrap();
+        let parsed: TypedData = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.encode_eip712().unwrap(), sample().signing_hash().unwrap());
+        assert_eq!(parsed.primary_type, PRIMARY_TYPE);
+    }
+
+    #[test]
+    fn test_invalid_inputs_are_rejected() {
+        let mut bad_root = sample();
+        bad_root.merkle_root = "0x1234".to_string();
+        assert!(bad_root.signing_hash().is_err());
+
+        assert!(sample().recover("0xdeadbeef").is_err());
+    }
+}
@@ -1,17 +1,22 @@
 //! Merkle Tree for Dividend Distribution
 //!
 //! Generates Merkle proofs for gas-efficient on-chain dividend claims.
-//! Each leaf is: keccak256(keccak256(abi.encode(address, amount)))
+//! Each leaf is: keccak256(keccak256(abi.encode(...fields)))
 //! The double hashing corresponds to Solidity's:
 //! `keccak256(bytes.concat(keccak256(abi.encode(addr, amt))))`
 //!
+//! Which fields are encoded is decided by a `LeafSchema`. The default is
+//! `(address, uint256)`; schemas binding leaves to an epoch or asset let
+//! contracts reject proofs replayed from another distribution.
+//!
 //! The root is stored on-chain in the DividendVault contract.
 
 use tiny_keccak::{Hasher, Keccak};
 use ethers::abi::Token;
 use ethers::types::{Address, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::str::FromStr;
-use hex;
 use anyhow::{Result, anyhow};
 
 /// A leaf node in the Merkle tree representing a single claim.
@@ -21,90 +26,577 @@ pub struct ClaimLeaf {
     pub amount_wei: String, // Amount in wei (for precision)
 }
 
+/// Encoding of a claim into a Merkle leaf.
+///
+/// Implement this to support a contract with its own leaf layout; the builder and
+/// verifiers are generic over it. Built-in layouts are in `StandardLeafSchema`.
+pub trait LeafSchema: Send + Sync {
+    /// Solidity types of the encoded fields, e.g. `["address", "uint256"]`.
+    fn leaf_encoding(&self) -> &[&str];
+
+    /// ABI tokens for one claim, in `leaf_encoding` order.
+    fn encode(&self, address: Address, amount: U256) -> Vec<Token>;
+
+    /// Leaf hash: `keccak256(bytes.concat(keccak256(abi.encode(...))))`.
+    fn hash_leaf(&self, claim: &ClaimLeaf) -> Result<[u8; 32]> {
+        let addr = Address::from_str(&claim.wallet_address)
+            .map_err(|e| anyhow!("Invalid address format '{}': {}", claim.wallet_address, e))?;
+        let amt = U256::from_dec_str(&claim.amount_wei)
+            .map_err(|e| anyhow!("Invalid amount format '{}': {}", claim.amount_wei, e))?;
+
+        Ok(double_keccak(&ethers::abi::encode(&self.encode(addr, amt))))
+    }
+}
+
+/// Leaf layouts supported out of the box. Recorded per distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)] // Variants are named after the encoded fields
+pub enum StandardLeafSchema {
+    /// `abi.encode(address, uint256)`, as expected by DividendVault.
+    #[default]
+    AddressAmount,
+    /// `abi.encode(uint256 epoch, address, uint256)`.
+    EpochAddressAmount { epoch: u64 },
+    /// `abi.encode(address, address token, uint256)`.
+    AddressTokenAmount { token: Address },
+}
+
+impl LeafSchema for StandardLeafSchema {
+    fn leaf_encoding(&self) -> &[&str] {
+        match self {
+            Self::AddressAmount => &["address", "uint256"],
+            Self::EpochAddressAmount { .. } => &["uint256", "address", "uint256"],
+            Self::AddressTokenAmount { .. } => &["address", "address", "uint256"],
+        }
+    }
+
+    fn encode(&self, address: Address, amount: U256) -> Vec<Token> {
+        match self {
+            Self::AddressAmount => vec![Token::Address(address), Token::Uint(amount)],
+            Self::EpochAddressAmount { epoch } => vec![
+                Token::Uint(U256::from(*epoch)),
+                Token::Address(address),
+                Token::Uint(amount),
+            ],
+            Self::AddressTokenAmount { token } => vec![
+                Token::Address(address),
+                Token::Address(*token),
+                Token::Uint(amount),
+            ],
+        }
+    }
+}
+
+/// Record of several claims for the same address that were merged into one leaf.
+///
+/// DividendVault tracks `claimed[epoch][address]`, so a second leaf for the same
+/// address could never be claimed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedLeaf {
+    pub wallet_address: String,
+    /// Individual amounts that were summed, in input order.
+    pub amounts_wei: Vec<String>,
+    pub total_wei: String,
+}
+
+/// Merge claims that share an address (case-insensitive) and sort them canonically.
+///
+/// Addresses are normalized to lowercase hex and leaves are ordered by address bytes,
+/// so the same set of claims always yields the same leaf list regardless of input order.
+/// Returns the canonical leaves and a record of every address that was merged.
+pub fn canonicalize_claims(claims: &[ClaimLeaf]) -> Result<(Vec<ClaimLeaf>, Vec<MergedLeaf>)> {
+    let mut by_address: BTreeMap<Address, Vec<U256>> = BTreeMap::new();
+
+    for claim in claims {
+        let addr = Address::from_str(&claim.wallet_address)
+            .map_err(|e| anyhow!("Invalid address format '{}': {}", claim.wallet_address, e))?;
+        let amt = U256::from_dec_str(&claim.amount_wei)
+            .map_err(|e| anyhow!("Invalid amount format '{}': {}", claim.amount_wei, e))?;
+
+        by_address.entry(addr).or_default().push(amt);
+    }
+
+    let mut leaves = Vec::with_capacity(by_address.len());
+    let mut merged = Vec::new();
+
+    for (addr, amounts) in by_address {
+        let mut total = U256::zero();
+        for amt in &amounts {
+            total = total
+                .checked_add(*amt)
+                .ok_or_else(|| anyhow!("Amount overflow merging claims for {:#x}", addr))?;
+        }
+
+        let wallet_address = format!("{:#x}", addr);
+        if amounts.len() > 1 {
+            merged.push(MergedLeaf {
+                wallet_address: wallet_address.clone(),
+                amounts_wei: amounts.iter().map(|a| a.to_string()).collect(),
+                total_wei: total.to_string(),
+            });
+        }
+
+        leaves.push(ClaimLeaf {
+            wallet_address,
+            amount_wei: total.to_string(),
+        });
+    }
+
+    Ok((leaves, merged))
+}
+
 /// Build a Merkle tree from a list of claims and return (root, proofs).
+///
+/// Convenience wrapper over `MerkleTreeBuilder` that formats every proof up front.
+/// Fine for small trees; large distributions should use the builder directly and
+/// derive proofs on request. Leaves are sorted, so the root does not depend on input
+/// order; proofs are returned in input order. Uses the default `(address, uint256)`
+/// leaf schema.
+#[allow(dead_code)]
 pub fn build_merkle_tree(claims: &[ClaimLeaf]) -> Result<(String, Vec<Vec<String>>)> {
     if claims.is_empty() {
         return Ok((String::new(), vec![]));
     }
 
-    // Generate leaf hashes
-    let mut leaves = Vec::with_capacity(claims.len());
-    for claim in claims {
-        leaves.push(hash_leaf(&claim.wallet_address, &claim.amount_wei)?);
+    let mut builder = MerkleTreeBuilder::with_capacity(claims.len());
+    builder.extend(claims.iter().cloned())?;
+    let tree = builder.finish()?;
+
+    // Add "0x" prefix for consistency with standard tools
+    let root = format!("0x{}", hex::encode(tree.root()));
+
+    let proofs = (0..tree.len())
+        .map(|i| {
+            tree.proof(i)
+                .unwrap_or_default()
+                .iter()
+                .map(|node| format!("0x{}", hex::encode(node)))
+                .collect()
+        })
+        .collect();
+
+    Ok((root, proofs))
+}
+
+/// Number of claims buffered before a batch is hashed in parallel.
+const HASH_BATCH_SIZE: usize = 16_384;
+
+/// Tree levels narrower than this are hashed on the calling thread.
+const PARALLEL_LEVEL_THRESHOLD: usize = 4_096;
+
+/// Incremental builder for trees in OpenZeppelin array layout
+/// (root at 0, children of `i` at `2i + 1` and `2i + 2`, no padding).
+///
+/// Claims are buffered in fixed-size batches and hashed across threads, so while
+/// streaming only the 32-byte leaf hashes are retained, never the claims themselves.
+/// Leaves can come from any iterator or be pushed one by one from a database cursor.
+/// `finish()` sorts leaf hashes (as `StandardMerkleTree` does), lays out `2n - 1`
+/// nodes and hashes each level in parallel. Peak memory is roughly 104 bytes per leaf.
+pub struct MerkleTreeBuilder<S: LeafSchema = StandardLeafSchema> {
+    schema: S,
+    batch: Vec<ClaimLeaf>,
+    hashes: Vec<[u8; 32]>,
+    threads: usize,
+}
+
+impl Default for MerkleTreeBuilder {
+    fn default() -> Self {
+        Self::with_capacity(0)
     }
+}
 
-    // Pad to power of 2
-    let target_len = leaves.len().next_power_of_two();
-    while leaves.len() < target_len {
-        leaves.push([0u8; 32]);
+impl MerkleTreeBuilder {
+    /// Create a builder for `(address, uint256)` leaves, reserving space for `leaves`
+    /// leaf hashes if the count is known.
+    pub fn with_capacity(leaves: usize) -> Self {
+        Self::with_schema(StandardLeafSchema::AddressAmount, leaves)
     }
+}
 
-    let num_leaves = leaves.len();
-    let mut tree: Vec<[u8; 32]> = vec![[0u8; 32]; 2 * num_leaves];
+impl<S: LeafSchema> MerkleTreeBuilder<S> {
+    /// Create a builder that encodes leaves with `schema`.
+    pub fn with_schema(schema: S, leaves: usize) -> Self {
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        Self {
+            schema,
+            batch: Vec::with_capacity(HASH_BATCH_SIZE.min(leaves.max(1))),
+            hashes: Vec::with_capacity(leaves),
+            threads,
+        }
+    }
 
-    // Fill leaves
-    for (i, leaf) in leaves.iter().enumerate() {
-        tree[num_leaves + i] = *leaf;
+    /// Add one claim. Hashing happens when a full batch has accumulated.
+    pub fn push(&mut self, claim: ClaimLeaf) -> Result<()> {
+        self.batch.push(claim);
+        if self.batch.len() >= HASH_BATCH_SIZE {
+            self.flush()?;
+        }
+        Ok(())
     }
 
-    // Build tree bottom-up
-    for i in (1..num_leaves).rev() {
-        tree[i] = hash_pair(&tree[2 * i], &tree[2 * i + 1]);
+    /// Add claims from an iterator without collecting them first.
+    pub fn extend<I: IntoIterator<Item = ClaimLeaf>>(&mut self, claims: I) -> Result<()> {
+        for claim in claims {
+            self.push(claim)?;
+        }
+        Ok(())
     }
 
-    let root = hex::encode(tree[1]);
+    /// Number of claims added so far.
+    pub fn len(&self) -> usize {
+        self.hashes.len() + self.batch.len()
+    }
 
-    // Add "0x" prefix for consistency with standard tools
-    let root = format!("0x{}", root);
+    /// Lay out and hash the tree.
+    pub fn finish(mut self) -> Result<MerkleTree> {
+        self.flush()?;
+
+        let leaf_count = self.hashes.len();
+        if leaf_count == 0 {
+            return Err(anyhow!("Cannot build a Merkle tree without leaves"));
+        }
+        if leaf_count > u32::MAX as usize / 2 {
+            return Err(anyhow!("Too many leaves for one tree: {}", leaf_count));
+        }
+
+        // Sort leaves by hash so the root only depends on the set of claims
+        let hashes = std::mem::take(&mut self.hashes);
+        let mut order: Vec<u32> = (0..leaf_count as u32).collect();
+        order.sort_unstable_by(|a, b| hashes[*a as usize].cmp(&hashes[*b as usize]));
+
+        // Sorted leaves fill the array from the end backwards
+        let tree_len = 2 * leaf_count - 1;
+        let mut nodes = vec![[0u8; 32]; tree_len];
+        let mut leaf_indices = vec![0u32; leaf_count];
+        for (position, value_index) in order.into_iter().enumerate() {
+            let tree_index = tree_len - 1 - position;
+            nodes[tree_index] = hashes[value_index as usize];
+            leaf_indices[value_index as usize] = tree_index as u32;
+        }
+        drop(hashes);
 
-    // Generate proofs for each original claim
-    let mut proofs = Vec::new();
-    for i in 0..claims.len() {
+        hash_levels(&mut nodes, leaf_count, self.threads);
+
+        Ok(MerkleTree { nodes, leaf_indices })
+    }
+
+    /// Hash the buffered batch across threads and keep only the leaf hashes.
+    fn flush(&mut self) -> Result<()> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+
+        let chunk_size = self.batch.len().div_ceil(self.threads).max(1);
+        let batch = &self.batch;
+        let schema = &self.schema;
+        let hashed: Vec<Result<Vec<[u8; 32]>>> = std::thread::scope(|scope| {
+            let workers: Vec<_> = batch
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|c| schema.hash_leaf(c))
+                            .collect()
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .map(|w| w.join().unwrap_or_else(|_| Err(anyhow!("Leaf hashing thread panicked"))))
+                .collect()
+        });
+
+        for chunk in hashed {
+            self.hashes.extend(chunk?);
+        }
+        self.batch.clear();
+        Ok(())
+    }
+}
+
+/// Hash internal nodes level by level, deepest first.
+///
+/// Level `L` of the array layout spans `[2^L - 1, 2^(L+1) - 1)`, and all children of a
+/// level lie after it, so each level can be split across threads without locking.
+fn hash_levels(nodes: &mut [[u8; 32]], leaf_count: usize, threads: usize) {
+    let internal = nodes.len() - leaf_count;
+
+    let mut level_starts = Vec::new();
+    let mut start = 0;
+    while start < internal {
+        level_starts.push(start);
+        start = 2 * start + 1;
+    }
+
+    for &start in level_starts.iter().rev() {
+        let end = (2 * start + 1).min(internal);
+        let (head, children) = nodes.split_at_mut(end);
+        let level = &mut head[start..end];
+
+        if threads <= 1 || level.len() < PARALLEL_LEVEL_THRESHOLD {
+            hash_level(level, start, children, end);
+            continue;
+        }
+
+        let chunk_size = level.len().div_ceil(threads);
+        let children = &*children;
+        std::thread::scope(|scope| {
+            for (i, chunk) in level.chunks_mut(chunk_size).enumerate() {
+                scope.spawn(move || hash_level(chunk, start + i * chunk_size, children, end));
+            }
+        });
+    }
+}
+
+/// Hash a run of nodes starting at array index `first`; `children` starts at array index `offset`.
+fn hash_level(level: &mut [[u8; 32]], first: usize, children: &[[u8; 32]], offset: usize) {
+    for (j, node) in level.iter_mut().enumerate() {
+        let left = 2 * (first + j) + 1 - offset;
+        *node = hash_pair(&children[left], &children[left + 1]);
+    }
+}
+
+/// A tree in OpenZeppelin array layout, as produced by `MerkleTreeBuilder`. Proofs are
+/// produced on demand as raw nodes.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    nodes: Vec<[u8; 32]>,
+    /// Array index of each leaf, by insertion order.
+    leaf_indices: Vec<u32>,
+}
+
+impl MerkleTree {
+    pub fn root(&self) -> [u8; 32] {
+        self.nodes[0]
+    }
+
+    /// Number of leaves.
+    pub fn len(&self) -> usize {
+        self.leaf_indices.len()
+    }
+
+    /// Sibling path for the claim at `value_index` (insertion order), bottom-up.
+    pub fn proof(&self, value_index: usize) -> Option<Vec<[u8; 32]>> {
+        let mut index = *self.leaf_indices.get(value_index)? as usize;
         let mut proof = Vec::new();
-        let mut idx = num_leaves + i;
+        while index > 0 {
+            let sibling = if index % 2 == 1 { index + 1 } else { index - 1 };
+            proof.push(self.nodes[sibling]);
+            index = (index - 1) / 2;
+        }
+        Some(proof)
+    }
+
+    /// Split into the node array and per-claim leaf indices.
+    pub fn into_parts(self) -> (Vec<[u8; 32]>, Vec<u32>) {
+        (self.nodes, self.leaf_indices)
+    }
+}
+
+/// Verify a Merkle proof for a claim against a root.
+///
+/// Mirrors OpenZeppelin's `MerkleProof.verify`: the leaf is double-hashed and
+/// folded with each proof element using sorted-pair hashing, so a `true` result
+/// means `DividendVault.claimDividend` will accept the same (amount, proof).
+pub fn verify_proof<S: LeafSchema + ?Sized>(
+    schema: &S,
+    root: &str,
+    leaf: &ClaimLeaf,
+    proof: &[String],
+) -> Result<bool> {
+    let root = parse_node(root)?;
+
+    let mut computed = schema.hash_leaf(leaf)?;
+    for element in proof {
+        computed = hash_pair(&computed, &parse_node(element)?);
+    }
+
+    Ok(computed == root)
+}
+
+/// A multiproof for several leaves of one tree, as consumed by
+/// OpenZeppelin's `MerkleProof.multiProofVerify(proof, proofFlags, root, leaves)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiProof {
+    /// Leaf hashes in the order the contract expects them.
+    pub leaves: Vec<[u8; 32]>,
+    pub proof: Vec<[u8; 32]>,
+    pub proof_flags: Vec<bool>,
+}
+
+/// Positions of a multiproof's nodes in a tree in OpenZeppelin array layout, so a
+/// caller holding the tree elsewhere only reads the nodes it needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiProofLayout {
+    /// Leaf positions in the order the contract expects them.
+    pub leaves: Vec<usize>,
+    /// Positions of the proof nodes.
+    pub proof: Vec<usize>,
+    pub proof_flags: Vec<bool>,
+}
 
-        while idx > 1 {
-            let sibling = if idx % 2 == 0 { idx + 1 } else { idx - 1 };
-            // Add "0x" prefix to proof elements
-            proof.push(format!("0x{}", hex::encode(tree[sibling])));
-            idx /= 2;
+/// Lay out a multiproof over a tree of `node_count` nodes in OpenZeppelin array layout
+/// (root at 0, children of `i` at `2i + 1` and `2i + 2`).
+///
+/// `tree_indices` are positions of leaves in the tree; the returned `leaves` are ordered
+/// by descending tree index, matching `@openzeppelin/merkle-tree`'s `getMultiProof`.
+pub fn multiproof_layout(node_count: usize, tree_indices: &[usize]) -> Result<MultiProofLayout> {
+    for &index in tree_indices {
+        if index >= node_count || 2 * index + 1 < node_count {
+            return Err(anyhow!("Index {} is not a leaf", index));
         }
+    }
 
-        proofs.push(proof);
+    let mut indices = tree_indices.to_vec();
+    indices.sort_unstable_by(|a, b| b.cmp(a));
+    if indices.windows(2).any(|w| w[0] == w[1]) {
+        return Err(anyhow!("Cannot prove duplicated index"));
     }
 
-    Ok((root, proofs))
+    let mut stack: std::collections::VecDeque<usize> = indices.iter().copied().collect();
+    let mut proof = Vec::new();
+    let mut proof_flags = Vec::new();
+
+    while let Some(&j) = stack.front() {
+        if j == 0 {
+            break;
+        }
+        stack.pop_front();
+
+        let sibling = if j % 2 == 1 { j + 1 } else { j - 1 };
+        if stack.front() == Some(&sibling) {
+            proof_flags.push(true);
+            stack.pop_front();
+        } else {
+            proof_flags.push(false);
+            proof.push(sibling);
+        }
+        stack.push_back((j - 1) / 2);
+    }
+
+    if indices.is_empty() {
+        proof.push(0);
+    }
+
+    Ok(MultiProofLayout {
+        leaves: indices,
+        proof,
+        proof_flags,
+    })
 }
 
-fn hash_leaf(address: &str, amount_wei: &str) -> Result<[u8; 32]> {
-    // 1. Parse inputs
-    let addr = Address::from_str(address)
-        .map_err(|e| anyhow!("Invalid address format '{}': {}", address, e))?;
+/// Recompute the root from a multiproof, mirroring `MerkleProof.processMultiProof`.
+pub fn process_multiproof(multiproof: &MultiProof) -> Result<[u8; 32]> {
+    let leaves = &multiproof.leaves;
+    let proof = &multiproof.proof;
+    let flags = &multiproof.proof_flags;
 
-    let amt = U256::from_dec_str(amount_wei)
-        .map_err(|e| anyhow!("Invalid amount format '{}': {}", amount_wei, e))?;
+    if leaves.len() + proof.len() != flags.len() + 1 {
+        return Err(anyhow!("Invalid multiproof: leaves + proof != flags + 1"));
+    }
 
-    // 2. ABI Encode: abi.encode(address, amount)
-    // This adds proper 32-byte padding to both arguments
-    let encoded = ethers::abi::encode(&[Token::Address(addr), Token::Uint(amt)]);
+    let mut hashes: Vec<[u8; 32]> = Vec::with_capacity(flags.len());
+    let (mut leaf_pos, mut hash_pos, mut proof_pos) = (0, 0, 0);
+
+    for &flag in flags {
+        let a = if leaf_pos < leaves.len() {
+            leaf_pos += 1;
+            leaves[leaf_pos - 1]
+        } else {
+            hash_pos += 1;
+            *hashes
+                .get(hash_pos - 1)
+                .ok_or_else(|| anyhow!("Invalid multiproof: not enough hashes"))?
+        };
+
+        let b = if flag {
+            if leaf_pos < leaves.len() {
+                leaf_pos += 1;
+                leaves[leaf_pos - 1]
+            } else {
+                hash_pos += 1;
+                *hashes
+                    .get(hash_pos - 1)
+                    .ok_or_else(|| anyhow!("Invalid multiproof: not enough hashes"))?
+            }
+        } else {
+            proof_pos += 1;
+            *proof
+                .get(proof_pos - 1)
+                .ok_or_else(|| anyhow!("Invalid multiproof: not enough proof elements"))?
+        };
+
+        hashes.push(hash_pair(&a, &b));
+    }
+
+    if let Some(root) = hashes.last() {
+        if proof_pos != proof.len() {
+            return Err(anyhow!("Invalid multiproof: unused proof elements"));
+        }
+        Ok(*root)
+    } else if let Some(leaf) = leaves.first() {
+        Ok(*leaf)
+    } else {
+        Ok(proof[0])
+    }
+}
 
-    // 3. Inner Hash: keccak256(...)
+/// Verify a multiproof for a set of claims against a root.
+///
+/// `claims` must be in the same order as the multiproof's `leaves`.
+pub fn verify_multiproof<S: LeafSchema + ?Sized>(
+    schema: &S,
+    root: &str,
+    claims: &[ClaimLeaf],
+    proof: &[String],
+    proof_flags: &[bool],
+) -> Result<bool> {
+    let multiproof = MultiProof {
+        leaves: claims
+            .iter()
+            .map(|c| schema.hash_leaf(c))
+            .collect::<Result<_>>()?,
+        proof: proof.iter().map(|p| parse_node(p)).collect::<Result<_>>()?,
+        proof_flags: proof_flags.to_vec(),
+    };
+
+    Ok(process_multiproof(&multiproof)? == parse_node(root)?)
+}
+
+/// Parse a "0x"-prefixed hex string into a 32-byte tree node.
+pub(crate) fn parse_node(value: &str) -> Result<[u8; 32]> {
+    let stripped = value.strip_prefix("0x").unwrap_or(value);
+    let bytes = hex::decode(stripped)
+        .map_err(|e| anyhow!("Invalid hex node '{}': {}", value, e))?;
+
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("Merkle node '{}' is not 32 bytes", value))
+}
+
+/// keccak256(keccak256(data)), matching Solidity's
+/// `keccak256(bytes.concat(keccak256(data)))`.
+fn double_keccak(data: &[u8]) -> [u8; 32] {
     let mut inner_hasher = Keccak::v256();
     let mut inner_hash = [0u8; 32];
-    inner_hasher.update(&encoded);
+    inner_hasher.update(data);
     inner_hasher.finalize(&mut inner_hash);
 
-    // 4. Outer Hash: keccak256(inner_hash)
-    // Matches Solidity: keccak256(bytes.concat(keccak256(...)))
     let mut outer_hasher = Keccak::v256();
     let mut outer_hash = [0u8; 32];
     outer_hasher.update(&inner_hash);
     outer_hasher.finalize(&mut outer_hash);
 
-    Ok(outer_hash)
+    outer_hash
 }
 
-fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+pub(crate) fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
     let mut hasher = Keccak::v256();
     // Sort to ensure deterministic ordering (Zeppelin standard)
     if left <= right {
@@ -124,6 +616,8 @@ fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
 mod tests {
     use super::*;
 
+    const SCHEMA: StandardLeafSchema = StandardLeafSchema::AddressAmount;
+
     #[test]
     fn test_merkle_tree_single_claim() {
         let claims = vec![ClaimLeaf {
@@ -158,8 +652,10 @@ mod tests {
         let (root, proofs) = build_merkle_tree(&claims).expect("Failed to build merkle tree");
         assert!(!root.is_empty());
         assert_eq!(proofs.len(), 3);
-        // Each proof should have log2(4) = 2 elements (padded to 4 leaves)
-        assert_eq!(proofs[0].len(), 2);
+        // No padding: 3 leaves form a 5-node tree, one leaf sits directly under the root
+        let mut lengths: Vec<usize> = proofs.iter().map(|p| p.len()).collect();
+        lengths.sort();
+        assert_eq!(lengths, vec![1, 2, 2]);
     }
 
     #[test]
@@ -194,6 +690,276 @@ mod tests {
         assert_eq!(proofs[1][0], "0x68ac16a2532f97e96240f8ecf32dd7c2c94330f78c43d505288b9a7dace30882");
     }
 
+    #[test]
+    fn test_verify_proof_regression_vectors() {
+        let root = "0x8140f9815bda3adf6750884e0c94c193c9be3e5891d90d97d4e73934124ec5b1";
+        let leaf = ClaimLeaf {
+            wallet_address: "0x71C7656EC7ab88b098defB751B7401B5f6d8976F".to_string(),
+            amount_wei: "1000000000000000000".to_string(),
+        };
+        let proof = vec![
+            "0x08d32c0b719aa7d191069df3aa4963442b48ab22b642e50b485c5be6e0450df5".to_string(),
+        ];
+
+        assert!(verify_proof(&SCHEMA, root, &leaf, &proof).unwrap());
+
+        // Same proof with a different amount must fail, as it would on-chain
+        let inflated = ClaimLeaf {
+            amount_wei: "2000000000000000000".to_string(),
+            ..leaf
+        };
+        assert!(!verify_proof(&SCHEMA, root, &inflated, &proof).unwrap());
+    }
+
+    #[test]
+    fn test_verify_proof_round_trip() {
+        let claims = vec![
+            ClaimLeaf {
+                wallet_address: "0x71C7656EC7ab88b098defB751B7401B5f6d8976F".to_string(),
+                amount_wei: "1000000".to_string(),
+            },
+            ClaimLeaf {
+                wallet_address: "0xeb8da55a0aa150d18b973523cf305342eb35197f".to_string(),
+                amount_wei: "2000000".to_string(),
+            },
+            ClaimLeaf {
+                wallet_address: "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1".to_string(),
+                amount_wei: "3000000".to_string(),
+            },
+        ];
+
+        let (root, proofs) = build_merkle_tree(&claims).expect("Failed to build merkle tree");
+        for (claim, proof) in claims.iter().zip(&proofs) {
+            assert!(verify_proof(&SCHEMA, &root, claim, proof).unwrap());
+        }
+
+        // A proof belonging to another leaf is rejected
+        assert!(!verify_proof(&SCHEMA, &root, &claims[0], &proofs[1]).unwrap());
+    }
+
+    #[test]
+    fn test_verify_proof_malformed_node_returns_error() {
+        let leaf = ClaimLeaf {
+            wallet_address: "0x71C7656EC7ab88b098defB751B7401B5f6d8976F".to_string(),
+            amount_wei: "100".to_string(),
+        };
+
+        assert!(verify_proof(&SCHEMA, "0x1234", &leaf, &[]).is_err());
+    }
+
+    #[test]
+    fn test_merkle_tree_independent_of_input_order() {
+        let claims = vec![
+            ClaimLeaf {
+                wallet_address: "0x71C7656EC7ab88b098defB751B7401B5f6d8976F".to_string(),
+                amount_wei: "1000000".to_string(),
+            },
+            ClaimLeaf {
+                wallet_address: "0xeb8da55a0aa150d18b973523cf305342eb35197f".to_string(),
+                amount_wei: "2000000".to_string(),
+            },
+            ClaimLeaf {
+                wallet_address: "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1".to_string(),
+                amount_wei: "3000000".to_string(),
+            },
+        ];
+        let mut reversed = claims.clone();
+        reversed.reverse();
+
+        let (root, proofs) = build_merkle_tree(&claims).unwrap();
+        let (reversed_root, reversed_proofs) = build_merkle_tree(&reversed).unwrap();
+
+        assert_eq!(root, reversed_root);
+        // Proofs still follow input order
+        assert_eq!(proofs[0], reversed_proofs[2]);
+        assert!(verify_proof(&SCHEMA, &root, &claims[0], &proofs[0]).unwrap());
+    }
+
+    #[test]
+    fn test_multiproof_layout_names_only_needed_nodes() {
+        // Leaves 3 and 4 are siblings, so only their parent's sibling is needed
+        let layout = multiproof_layout(7, &[3, 4]).unwrap();
+        assert_eq!(layout.leaves, vec![4, 3]);
+        assert_eq!(layout.proof, vec![2]);
+        assert_eq!(layout.proof_flags, vec![true, false]);
+
+        assert!(multiproof_layout(7, &[2]).is_err());
+        assert!(multiproof_layout(7, &[5, 5]).is_err());
+    }
+
+    #[test]
+    fn test_canonicalize_merges_duplicate_addresses() {
+        let claims = vec![
+            ClaimLeaf {
+                wallet_address: "0xeb8da55a0aa150d18b973523cf305342eb35197f".to_string(),
+                amount_wei: "2000000".to_string(),
+            },
+            ClaimLeaf {
+                wallet_address: "0x71C7656EC7ab88b098defB751B7401B5f6d8976F".to_string(),
+                amount_wei: "1000000".to_string(),
+            },
+            ClaimLeaf {
+                // Same wallet as above, different casing (e.g. fee recipient and holder)
+                wallet_address: "0x71c7656ec7ab88b098defb751b7401b5f6d8976f".to_string(),
+                amount_wei: "500000".to_string(),
+            },
+        ];
+
+        let (leaves, merged) = canonicalize_claims(&claims).unwrap();
+
+        assert_eq!(leaves.len(), 2);
+        assert_eq!(leaves[0].wallet_address, "0x71c7656ec7ab88b098defb751b7401b5f6d8976f");
+        assert_eq!(leaves[0].amount_wei, "1500000");
+        assert_eq!(leaves[1].wallet_address, "0xeb8da55a0aa150d18b973523cf305342eb35197f");
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].amounts_wei, vec!["1000000", "500000"]);
+        assert_eq!(merged[0].total_wei, "1500000");
+    }
+
+    #[test]
+    fn test_canonicalize_is_order_independent() {
+        let claims = vec![
+            ClaimLeaf {
+                wallet_address: "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1".to_string(),
+                amount_wei: "3000000".to_string(),
+            },
+            ClaimLeaf {
+                wallet_address: "0x71C7656EC7ab88b098defB751B7401B5f6d8976F".to_string(),
+                amount_wei: "1000000".to_string(),
+            },
+        ];
+        let mut reversed = claims.clone();
+        reversed.reverse();
+
+        let (leaves, merged) = canonicalize_claims(&claims).unwrap();
+        let (reversed_leaves, _) = canonicalize_claims(&reversed).unwrap();
+
+        assert!(merged.is_empty());
+        for (a, b) in leaves.iter().zip(&reversed_leaves) {
+            assert_eq!(a.wallet_address, b.wallet_address);
+            assert_eq!(a.amount_wei, b.amount_wei);
+        }
+    }
+
+    /// Sibling path of the claim at `value_index`, bottom-up, as hex strings.
+    fn proof(tree: &MerkleTree, value_index: usize) -> Vec<String> {
+        tree.proof(value_index)
+            .unwrap()
+            .iter()
+            .map(|node| format!("0x{}", hex::encode(node)))
+            .collect()
+    }
+
+    fn synthetic_claims(count: usize) -> Vec<ClaimLeaf> {
+        (1..=count)
+            .map(|i| ClaimLeaf {
+                wallet_address: format!("0x{:040x}", i),
+                amount_wei: (i * 1_000).to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_builder_spans_parallel_batches() {
+        // Enough leaves for two hashing batches and parallel tree levels
+        let claims = synthetic_claims(HASH_BATCH_SIZE + 17);
+
+        let mut builder = MerkleTreeBuilder::default();
+        builder.extend(claims.iter().cloned()).unwrap();
+        assert_eq!(builder.len(), claims.len());
+        let tree = builder.finish().unwrap();
+
+        assert_eq!(tree.len(), claims.len());
+        let root = format!("0x{}", hex::encode(tree.root()));
+        for i in [0, 1, HASH_BATCH_SIZE, claims.len() - 1] {
+            assert!(verify_proof(&SCHEMA, &root, &claims[i], &proof(&tree, i)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_builder_matches_single_threaded_layout() {
+        let claims = synthetic_claims(PARALLEL_LEVEL_THRESHOLD * 3 + 5);
+
+        let mut parallel = MerkleTreeBuilder { threads: 4, ..Default::default() };
+        parallel.extend(claims.iter().cloned()).unwrap();
+
+        let mut serial = MerkleTreeBuilder { threads: 1, ..Default::default() };
+        serial.extend(claims.iter().cloned()).unwrap();
+
+        let (parallel, serial) = (parallel.finish().unwrap(), serial.finish().unwrap());
+        assert_eq!(parallel.root(), serial.root());
+        assert_eq!(parallel.leaf_indices, serial.leaf_indices);
+    }
+
+    #[test]
+    fn test_builder_rejects_empty_and_invalid() {
+        assert!(MerkleTreeBuilder::default().finish().is_err());
+
+        let mut builder = MerkleTreeBuilder::default();
+        builder
+            .push(ClaimLeaf {
+                wallet_address: "invalid_address".to_string(),
+                amount_wei: "100".to_string(),
+            })
+            .unwrap();
+        assert!(builder.finish().is_err());
+    }
+
+    #[test]
+    fn test_schema_encodings_match_abi_layout() {
+        let address = Address::from_str("0x71C7656EC7ab88b098defB751B7401B5f6d8976F").unwrap();
+        let token = Address::from_str("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap();
+        let amount = U256::from(1_000_000u64);
+
+        let encoded = ethers::abi::encode(&StandardLeafSchema::EpochAddressAmount { epoch: 7 }.encode(address, amount));
+        assert_eq!(encoded.len(), 96);
+        assert_eq!(encoded[31], 7);
+        assert_eq!(&encoded[44..64], address.as_bytes());
+
+        let encoded = ethers::abi::encode(&StandardLeafSchema::AddressTokenAmount { token }.encode(address, amount));
+        assert_eq!(encoded.len(), 96);
+        assert_eq!(&encoded[12..32], address.as_bytes());
+        assert_eq!(&encoded[44..64], token.as_bytes());
+
+        assert_eq!(SCHEMA.leaf_encoding(), ["address", "uint256"]);
+        assert_eq!(
+            StandardLeafSchema::EpochAddressAmount { epoch: 7 }.leaf_encoding(),
+            ["uint256", "address", "uint256"]
+        );
+    }
+
+    #[test]
+    fn test_epoch_schema_rejects_cross_epoch_replay() {
+        let claims = synthetic_claims(5);
+        let epoch_3 = StandardLeafSchema::EpochAddressAmount { epoch: 3 };
+        let epoch_4 = StandardLeafSchema::EpochAddressAmount { epoch: 4 };
+
+        let mut builder = MerkleTreeBuilder::with_schema(epoch_3, claims.len());
+        builder.extend(claims.iter().cloned()).unwrap();
+        let tree = builder.finish().unwrap();
+        let root = format!("0x{}", hex::encode(tree.root()));
+        let proof = proof(&tree, 2);
+
+        assert!(verify_proof(&epoch_3, &root, &claims[2], &proof).unwrap());
+        assert!(!verify_proof(&epoch_4, &root, &claims[2], &proof).unwrap());
+        assert!(!verify_proof(&SCHEMA, &root, &claims[2], &proof).unwrap());
+
+        let mut default = MerkleTreeBuilder::default();
+        default.extend(claims.iter().cloned()).unwrap();
+        assert_ne!(default.finish().unwrap().root(), tree.root());
+    }
+
+    #[test]
+    fn test_schema_serde_round_trip() {
+        let schema: StandardLeafSchema =
+            serde_json::from_str(r#"{"type":"epoch_address_amount","epoch":12}"#).unwrap();
+        assert_eq!(schema, StandardLeafSchema::EpochAddressAmount { epoch: 12 });
+
+        let json = serde_json::to_value(StandardLeafSchema::default()).unwrap();
+        assert_eq!(json, serde_json::json!({ "type": "address_amount" }));
+    }
+
     #[test]
     fn test_invalid_address_returns_error() {
         let claims = vec![ClaimLeaf {
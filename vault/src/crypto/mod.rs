@@ -1 +1,4 @@
+pub mod attestation;
 pub mod merkle;
+pub mod standard_tree;
+pub mod voucher;
@@ -0,0 +1,692 @@
+//! OpenZeppelin StandardMerkleTree Interop
+//!
+//! Builds trees in the array layout used by `@openzeppelin/merkle-tree` and reads/writes
+//! its JSON dump format (`standard-v1`), so distributions can be checked with
+//! off-the-shelf JS tooling and trees built in JS can be imported into the Vault.
+//!
+//! Layout: a complete binary tree stored in an array of `2n - 1` nodes, root at index 0,
+//! children of `i` at `2i + 1` and `2i + 2`. Leaf hashes are sorted ascending and placed
+//! from the end of the array backwards. Leaves use the same double-keccak encoding as
+//! DividendVault's `claimDividend`, so proofs verify against it unchanged.
+//!
+//! Trees are built with one of the `StandardLeafSchema` layouts. Epoch and token are
+//! constant within a tree, but are written into every dumped value as the JS library expects.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::merkle::{
+    hash_pair, multiproof_layout, parse_node, ClaimLeaf, LeafSchema, MerkleTreeBuilder,
+    StandardLeafSchema,
+};
+
+/// Dump format identifier written by `StandardMerkleTree.dump()`.
+pub const FORMAT: &str = "standard-v1";
+
+/// JSON dump compatible with `StandardMerkleTree.load()` / `.dump()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardTreeDump {
+    pub format: String,
+    pub leaf_encoding: Vec<String>,
+    pub tree: Vec<String>,
+    pub values: Vec<StandardTreeValue>,
+}
+
+/// A single leaf value and its position in the `tree` array.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardTreeValue {
+    /// Fields in the schema's `leafEncoding` order, e.g. `[address, amount]`.
+    /// Integers may be JSON strings or numbers on import.
+    pub value: Vec<serde_json::Value>,
+    pub tree_index: usize,
+}
+
+/// A multiproof over claims of one tree, ready for `MerkleProof.multiProofVerify`.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct StandardMultiProof {
+    /// Insertion-order indices of the proven claims, in contract leaf order.
+    pub value_indices: Vec<usize>,
+    pub proof: Vec<String>,
+    pub proof_flags: Vec<bool>,
+}
+
+/// A Merkle tree in OpenZeppelin StandardMerkleTree layout.
+#[derive(Debug, Clone)]
+pub struct StandardMerkleTree {
+    schema: StandardLeafSchema,
+    tree: Vec<[u8; 32]>,
+    /// Claims in insertion order, with the array index of their leaf.
+    values: Vec<(ClaimLeaf, usize)>,
+}
+
+impl StandardMerkleTree {
+    /// Build a tree from claims, matching `StandardMerkleTree.of(values, leafEncoding)`.
+    pub fn of(schema: StandardLeafSchema, claims: &[ClaimLeaf]) -> Result<Self> {
+        let mut builder = MerkleTreeBuilder::with_schema(schema, claims.len());
+        builder.extend(claims.iter().cloned())?;
+        let (tree, leaf_indices) = builder.finish()?.into_parts();
+
+        let values = claims
+            .iter()
+            .cloned()
+            .zip(leaf_indices.into_iter().map(|i| i as usize))
+            .collect();
+        Ok(Self { schema, tree, values })
+    }
+
+    /// Load and validate a dump produced by `StandardMerkleTree.dump()`.
+    ///
+    /// The leaf schema is inferred from `leafEncoding`; every value must carry the same
+    /// epoch or token. Rejects dumps with a foreign format or leaf encoding, a tree that
+    /// is not exactly `2n - 1` nodes for `n` values, missing or repeated leaf indices,
+    /// leaves that do not hash to their `treeIndex` node, and internal nodes that are
+    /// not the hash of their children.
+    pub fn load(dump: &StandardTreeDump) -> Result<Self> {
+        if dump.format != FORMAT {
+            return Err(anyhow!("Unsupported tree format '{}'", dump.format));
+        }
+        if dump.tree.is_empty() || dump.values.is_empty() {
+            return Err(anyhow!("Tree dump is empty"));
+        }
+
+        let schema = schema_from_dump(dump)?;
+
+        let tree = dump
+            .tree
+            .iter()
+            .map(|node| parse_node(node))
+            .collect::<Result<Vec<_>>>()?;
+
+        let values = dump
+            .values
+            .iter()
+            .map(|entry| Ok((value_to_claim(&schema, &entry.value)?, entry.tree_index)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Self::validated(schema, tree, values)
+    }
+
+    /// Rebuild a tree from `to_bytes()` output and all of its claims with their tree
+    /// indices. Runs the same integrity checks as `load`.
+    pub fn from_bytes(
+        schema: StandardLeafSchema,
+        bytes: &[u8],
+        values: Vec<(ClaimLeaf, usize)>,
+    ) -> Result<Self> {
+        if bytes.is_empty() || !bytes.len().is_multiple_of(32) {
+            return Err(anyhow!("Tree blob length {} is not a multiple of 32", bytes.len()));
+        }
+
+        let tree = bytes
+            .chunks_exact(32)
+            .map(|chunk| {
+                let mut node = [0u8; 32];
+                node.copy_from_slice(chunk);
+                node
+            })
+            .collect();
+
+        Self::validated(schema, tree, values)
+    }
+
+    /// Compact binary form: the `2n - 1` nodes concatenated, 32 bytes each, in array order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.tree.concat()
+    }
+
+    /// Check the tree's shape, that internal nodes hash from their children and that
+    /// every value hashes to its own leaf.
+    ///
+    /// A tree of `n` values must have exactly `2n - 1` nodes, with the values occupying
+    /// each leaf slot `n - 1 ..= 2n - 2` once, so every node is covered by the checks.
+    fn validated(
+        schema: StandardLeafSchema,
+        tree: Vec<[u8; 32]>,
+        values: Vec<(ClaimLeaf, usize)>,
+    ) -> Result<Self> {
+        let leaf_count = values.len();
+        if leaf_count == 0 || tree.len() != 2 * leaf_count - 1 {
+            return Err(anyhow!(
+                "Tree of {} nodes cannot hold {} leaves",
+                tree.len(),
+                leaf_count
+            ));
+        }
+
+        // Every internal node must be the hash of its two children
+        for i in 0..leaf_count - 1 {
+            let (left, right) = match (tree.get(2 * i + 1), tree.get(2 * i + 2)) {
+                (Some(left), Some(right)) => (left, right),
+                _ => return Err(anyhow!("Tree node {} is missing a child", i)),
+            };
+            if tree[i] != hash_pair(left, right) {
+                return Err(anyhow!("Tree node {} does not match its children", i));
+            }
+        }
+
+        let mut seen = vec![false; leaf_count];
+        for (claim, tree_index) in &values {
+            let slot = tree_index
+                .checked_sub(leaf_count - 1)
+                .filter(|slot| *slot < leaf_count)
+                .ok_or_else(|| anyhow!("treeIndex {} is not a leaf", tree_index))?;
+            if std::mem::replace(&mut seen[slot], true) {
+                return Err(anyhow!("treeIndex {} is used more than once", tree_index));
+            }
+
+            if schema.hash_leaf(claim)? != tree[*tree_index] {
+                return Err(anyhow!(
+                    "Leaf for {} does not match tree node {}",
+                    claim.wallet_address,
+                    tree_index
+                ));
+            }
+        }
+
+        Ok(Self { schema, tree, values })
+    }
+
+    /// Serialize to the `standard-v1` JSON dump format.
+    pub fn dump(&self) -> StandardTreeDump {
+        StandardTreeDump {
+            format: FORMAT.to_string(),
+            leaf_encoding: self.schema.leaf_encoding().iter().map(|s| s.to_string()).collect(),
+            tree: self.tree.iter().map(|node| format!("0x{}", hex::encode(node))).collect(),
+            values: self
+                .values
+                .iter()
+                .map(|(claim, tree_index)| StandardTreeValue {
+                    value: claim_to_value(&self.schema, claim),
+                    tree_index: *tree_index,
+                })
+                .collect(),
+        }
+    }
+
+    /// Leaf schema the tree was built with.
+    pub fn schema(&self) -> StandardLeafSchema {
+        self.schema
+    }
+
+    /// Merkle root as a "0x"-prefixed hex string.
+    pub fn root(&self) -> String {
+        format!("0x{}", hex::encode(self.tree[0]))
+    }
+
+    /// Claims in insertion order.
+    pub fn claims(&self) -> impl Iterator<Item = &ClaimLeaf> {
+        self.values.iter().map(|(claim, _)| claim)
+    }
+
+    /// Number of leaves in the tree.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Array index of the leaf for the claim at `value_index`.
+    pub fn tree_index(&self, value_index: usize) -> Option<usize> {
+        self.values.get(value_index).map(|(_, tree_index)| *tree_index)
+    }
+
+    /// Multiproof for several claims (insertion-order indices), matching
+    /// `StandardMerkleTree.getMultiProof()`. The API reads the same nodes from the
+    /// stored tree instead of loading it.
+    #[allow(dead_code)]
+    pub fn multiproof(&self, value_indices: &[usize]) -> Result<StandardMultiProof> {
+        let mut tree_indices = Vec::with_capacity(value_indices.len());
+        let mut by_tree_index = std::collections::HashMap::with_capacity(value_indices.len());
+        for &value_index in value_indices {
+            let (_, tree_index) = self
+                .values
+                .get(value_index)
+                .ok_or_else(|| anyhow!("No leaf at index {}", value_index))?;
+            tree_indices.push(*tree_index);
+            by_tree_index.insert(*tree_index, value_index);
+        }
+
+        let layout = multiproof_layout(self.tree.len(), &tree_indices)?;
+
+        Ok(StandardMultiProof {
+            // Map the ordered leaves back to the claims they belong to
+            value_indices: layout.leaves.iter().map(|i| by_tree_index[i]).collect(),
+            proof: layout.proof.iter().map(|&i| format!("0x{}", hex::encode(self.tree[i]))).collect(),
+            proof_flags: layout.proof_flags,
+        })
+    }
+}
+
+/// Array indices of the proof nodes for the leaf at `tree_index`, bottom-up.
+///
+/// Lets callers read only the `O(log n)` nodes they need from a stored tree.
+pub fn proof_path(mut tree_index: usize) -> Vec<usize> {
+    let mut path = Vec::new();
+    while tree_index > 0 {
+        let sibling = if tree_index % 2 == 1 { tree_index + 1 } else { tree_index - 1 };
+        path.push(sibling);
+        tree_index = (tree_index - 1) / 2;
+    }
+    path
+}
+
+/// Work out which built-in schema a dump uses from its `leafEncoding` and first value.
+fn schema_from_dump(dump: &StandardTreeDump) -> Result<StandardLeafSchema> {
+    let encoding: Vec<&str> = dump.leaf_encoding.iter().map(String::as_str).collect();
+    let first = &dump.values[0].value;
+
+    let schema = match encoding.as_slice() {
+        ["address", "uint256"] => StandardLeafSchema::AddressAmount,
+        ["uint256", "address", "uint256"] => StandardLeafSchema::EpochAddressAmount {
+            epoch: first
+                .first()
+                .and_then(|v| json_uint(v).ok())
+                .and_then(|epoch| epoch.parse().ok())
+                .ok_or_else(|| anyhow!("Epoch must be an unsigned 64-bit integer"))?,
+        },
+        ["address", "address", "uint256"] => StandardLeafSchema::AddressTokenAmount {
+            token: first
+                .get(1)
+                .and_then(|v| v.as_str())
+                .and_then(|token| token.parse().ok())
+                .ok_or_else(|| anyhow!("Token must be an address string"))?,
+        },
+        _ => return Err(anyhow!("Unsupported leaf encoding {:?}", dump.leaf_encoding)),
+    };
+
+    Ok(schema)
+}
+
+/// Dumped value for a claim, in the schema's `leafEncoding` order.
+fn claim_to_value(schema: &StandardLeafSchema, claim: &ClaimLeaf) -> Vec<serde_json::Value> {
+    use serde_json::Value;
+
+    let address = Value::String(claim.wallet_address.clone());
+    let amount = Value::String(claim.amount_wei.clone());
+
+    match schema {
+        StandardLeafSchema::AddressAmount => vec![address, amount],
+        StandardLeafSchema::EpochAddressAmount { epoch } => {
+            vec![Value::String(epoch.to_string()), address, amount]
+        }
+        StandardLeafSchema::AddressTokenAmount { token } => {
+            vec![address, Value::String(format!("{:#x}", token)), amount]
+        }
+    }
+}
+
+/// Convert a dumped value into a claim leaf, checking that epoch or token match `schema`.
+fn value_to_claim(schema: &StandardLeafSchema, value: &[serde_json::Value]) -> Result<ClaimLeaf> {
+    let (address, amount) = match (schema, value) {
+        (StandardLeafSchema::AddressAmount, [address, amount]) => (address, amount),
+        (StandardLeafSchema::EpochAddressAmount { epoch }, [value_epoch, address, amount]) => {
+            if json_uint(value_epoch)?.parse::<u64>().ok() != Some(*epoch) {
+                return Err(anyhow!("All leaves must share epoch {}", epoch));
+            }
+            (address, amount)
+        }
+        (StandardLeafSchema::AddressTokenAmount { token }, [address, value_token, amount]) => {
+            let value_token: ethers::types::Address = value_token
+                .as_str()
+                .and_then(|t| t.parse().ok())
+                .ok_or_else(|| anyhow!("Token must be an address string"))?;
+            if value_token != *token {
+                return Err(anyhow!("All leaves must share token {:#x}", token));
+            }
+            (address, amount)
+        }
+        _ => {
+            return Err(anyhow!(
+                "Expected {:?} value, got {} fields",
+                schema.leaf_encoding(),
+                value.len()
+            ))
+        }
+    };
+
+    let wallet_address = address
+        .as_str()
+        .ok_or_else(|| anyhow!("Address must be a string"))?
+        .to_string();
+
+    Ok(ClaimLeaf {
+        wallet_address,
+        amount_wei: json_uint(amount)?,
+    })
+}
+
+/// Read a uint256 field as a decimal string.
+///
+/// JS tooling writes uint256 as a decimal string, but small values may arrive as numbers.
+fn json_uint(value: &serde_json::Value) -> Result<String> {
+    match value {
+        serde_json::Value::String(s) => Ok(s.clone()),
+        serde_json::Value::Number(n) if n.is_u64() => Ok(n.to_string()),
+        _ => Err(anyhow!("Integers must be decimal strings or unsigned integers")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::merkle::{verify_multiproof, verify_proof};
+
+    const SCHEMA: StandardLeafSchema = StandardLeafSchema::AddressAmount;
+
+    /// Proof for the claim at `value_index`, as `bytes32[]` hex strings.
+    fn proof(tree: &StandardMerkleTree, value_index: usize) -> Vec<String> {
+        proof_path(tree.tree_index(value_index).unwrap())
+            .into_iter()
+            .map(|i| format!("0x{}", hex::encode(tree.tree[i])))
+            .collect()
+    }
+
+    fn sample_claims() -> Vec<ClaimLeaf> {
+        vec![
+            ClaimLeaf {
+                wallet_address: "0x71C7656EC7ab88b098defB751B7401B5f6d8976F".to_string(),
+                amount_wei: "1000000".to_string(),
+            },
+            ClaimLeaf {
+                wallet_address: "0xeb8da55a0aa150d18b973523cf305342eb35197f".to_string(),
+                amount_wei: "2000000".to_string(),
+            },
+            ClaimLeaf {
+                wallet_address: "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1".to_string(),
+                amount_wei: "3000000".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_two_leaf_root_matches_regression_vector() {
+        // These values are used in `contracts/test/MerkleCompatibility.test.ts`.
+        // If you change this test, you MUST update the Solidity test as well.
+        let claims = vec![
+            ClaimLeaf {
+                wallet_address: "0x71C7656EC7ab88b098defB751B7401B5f6d8976F".to_string(),
+                amount_wei: "1000000000000000000".to_string(),
+            },
+            ClaimLeaf {
+                wallet_address: "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_string(),
+                amount_wei: "5000000000000000000".to_string(),
+            },
+        ];
+
+        let tree = StandardMerkleTree::of(SCHEMA, &claims).unwrap();
+        assert_eq!(tree.root(), "0x8140f9815bda3adf6750884e0c94c193c9be3e5891d90d97d4e73934124ec5b1");
+        assert_eq!(
+            proof(&tree, 0),
+            vec!["0x08d32c0b719aa7d191069df3aa4963442b48ab22b642e50b485c5be6e0450df5"]
+        );
+        assert_eq!(
+            proof(&tree, 1),
+            vec!["0x68ac16a2532f97e96240f8ecf32dd7c2c94330f78c43d505288b9a7dace30882"]
+        );
+    }
+
+    #[test]
+    fn test_proofs_verify_against_root() {
+        let claims = sample_claims();
+        let tree = StandardMerkleTree::of(SCHEMA, &claims).unwrap();
+
+        for (i, claim) in claims.iter().enumerate() {
+            assert!(verify_proof(&SCHEMA, &tree.root(), claim, &proof(&tree, i)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_multiproof_verifies() {
+        let claims = sample_claims();
+        let tree = StandardMerkleTree::of(SCHEMA, &claims).unwrap();
+
+        for indices in [vec![0], vec![0, 2], vec![2, 1], vec![0, 1, 2]] {
+            let multiproof = tree.multiproof(&indices).unwrap();
+            let leaves: Vec<ClaimLeaf> = multiproof
+                .value_indices
+                .iter()
+                .map(|&i| claims[i].clone())
+                .collect();
+
+            assert_eq!(leaves.len(), indices.len());
+            assert!(verify_multiproof(
+                &SCHEMA,
+                &tree.root(),
+                &leaves,
+                &multiproof.proof,
+                &multiproof.proof_flags
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn test_multiproof_rejects_tampered_leaf() {
+        let claims = sample_claims();
+        let tree = StandardMerkleTree::of(SCHEMA, &claims).unwrap();
+        let multiproof = tree.multiproof(&[0, 1]).unwrap();
+
+        let mut leaves: Vec<ClaimLeaf> = multiproof
+            .value_indices
+            .iter()
+            .map(|&i| claims[i].clone())
+            .collect();
+        leaves[0].amount_wei = "1".to_string();
+
+        assert!(!verify_multiproof(&SCHEMA, &tree.root(), &leaves, &multiproof.proof, &multiproof.proof_flags)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_multiproof_rejects_duplicates() {
+        let tree = StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap();
+        assert!(tree.multiproof(&[1, 1]).is_err());
+        assert!(tree.multiproof(&[7]).is_err());
+    }
+
+    #[test]
+    fn test_dump_layout() {
+        let tree = StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap();
+        let dump = tree.dump();
+
+        assert_eq!(dump.format, "standard-v1");
+        assert_eq!(dump.leaf_encoding, vec!["address", "uint256"]);
+        assert_eq!(dump.tree.len(), 5);
+        assert_eq!(dump.tree[0], tree.root());
+
+        // Values keep insertion order and point at distinct leaf slots
+        let mut indices: Vec<usize> = dump.values.iter().map(|v| v.tree_index).collect();
+        assert_eq!(dump.values[0].value[1], "1000000");
+        indices.sort();
+        assert_eq!(indices, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_dump_load_round_trip() {
+        let tree = StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap();
+        let json = serde_json::to_string(&tree.dump()).unwrap();
+
+        let loaded = StandardMerkleTree::load(&serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(loaded.root(), tree.root());
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(proof(&loaded, 2), proof(&tree, 2));
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let claims = sample_claims();
+        let tree = StandardMerkleTree::of(SCHEMA, &claims).unwrap();
+        let bytes = tree.to_bytes();
+        assert_eq!(bytes.len(), 5 * 32);
+
+        let values = claims
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, claim)| (claim, tree.tree_index(i).unwrap()))
+            .collect();
+        let restored = StandardMerkleTree::from_bytes(SCHEMA, &bytes, values).unwrap();
+
+        assert_eq!(restored.root(), tree.root());
+        assert_eq!(proof(&restored, 1), proof(&tree, 1));
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_wrong_tree_index() {
+        let claims = sample_claims();
+        let tree = StandardMerkleTree::of(SCHEMA, &claims).unwrap();
+        let wrong = tree.tree_index(1).unwrap();
+
+        let mut values: Vec<(ClaimLeaf, usize)> = claims
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, claim)| (claim, tree.tree_index(i).unwrap()))
+            .collect();
+        values[0].1 = wrong;
+        values[1].1 = tree.tree_index(0).unwrap();
+
+        let result = StandardMerkleTree::from_bytes(SCHEMA, &tree.to_bytes(), values);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_proof_path_matches_proof() {
+        let tree = StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap();
+        let bytes = tree.to_bytes();
+
+        for i in 0..tree.len() {
+            let from_blob: Vec<String> = proof_path(tree.tree_index(i).unwrap())
+                .into_iter()
+                .map(|n| format!("0x{}", hex::encode(&bytes[n * 32..n * 32 + 32])))
+                .collect();
+            assert_eq!(from_blob, proof(&tree, i));
+        }
+    }
+
+    #[test]
+    fn test_load_accepts_numeric_amounts() {
+        let tree = StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap();
+        let mut dump = tree.dump();
+        dump.values[0].value[1] = serde_json::json!(1000000);
+
+        assert!(StandardMerkleTree::load(&dump).is_ok());
+    }
+
+    #[test]
+    fn test_load_rejects_tampered_amount() {
+        let tree = StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap();
+        let mut dump = tree.dump();
+        dump.values[0].value[1] = serde_json::json!("9000000");
+
+        assert!(StandardMerkleTree::load(&dump).is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_tampered_root() {
+        let tree = StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap();
+        let mut dump = tree.dump();
+        dump.tree[0] = format!("0x{}", "11".repeat(32));
+
+        assert!(StandardMerkleTree::load(&dump).is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_foreign_encoding() {
+        let tree = StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap();
+        let mut dump = tree.dump();
+        dump.leaf_encoding = vec!["address".to_string(), "uint128".to_string()];
+
+        assert!(StandardMerkleTree::load(&dump).is_err());
+    }
+
+    #[test]
+    fn test_epoch_schema_dump_round_trip() {
+        let schema = StandardLeafSchema::EpochAddressAmount { epoch: 42 };
+        let tree = StandardMerkleTree::of(schema, &sample_claims()).unwrap();
+        let dump = tree.dump();
+
+        assert_eq!(dump.leaf_encoding, vec!["uint256", "address", "uint256"]);
+        assert_eq!(dump.values[0].value[0], "42");
+        assert_ne!(tree.root(), StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap().root());
+
+        let loaded = StandardMerkleTree::load(&dump).unwrap();
+        assert_eq!(loaded.schema(), schema);
+        assert_eq!(loaded.root(), tree.root());
+        assert!(verify_proof(&schema, &tree.root(), &sample_claims()[1], &proof(&tree, 1)).unwrap());
+    }
+
+    #[test]
+    fn test_token_schema_dump_round_trip() {
+        let token = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".parse().unwrap();
+        let schema = StandardLeafSchema::AddressTokenAmount { token };
+        let tree = StandardMerkleTree::of(schema, &sample_claims()).unwrap();
+        let dump = tree.dump();
+
+        assert_eq!(dump.values[0].value[1], "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        assert_eq!(StandardMerkleTree::load(&dump).unwrap().schema(), schema);
+    }
+
+    #[test]
+    fn test_load_rejects_mixed_epochs() {
+        let schema = StandardLeafSchema::EpochAddressAmount { epoch: 1 };
+        let tree = StandardMerkleTree::of(schema, &sample_claims()).unwrap();
+        let mut dump = tree.dump();
+        dump.values[2].value[0] = serde_json::json!("2");
+
+        assert!(StandardMerkleTree::load(&dump).is_err());
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_wrong_schema() {
+        let claims = sample_claims();
+        let tree = StandardMerkleTree::of(SCHEMA, &claims).unwrap();
+        let values = claims
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, claim)| (claim, tree.tree_index(i).unwrap()))
+            .collect();
+        let epoch = StandardLeafSchema::EpochAddressAmount { epoch: 1 };
+
+        assert!(StandardMerkleTree::from_bytes(epoch, &tree.to_bytes(), values).is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_even_length_tree() {
+        // A single leaf with an unchecked "root" in front of it
+        let claim = &sample_claims()[0];
+        let leaf = SCHEMA.hash_leaf(claim).unwrap();
+        let dump = StandardTreeDump {
+            format: FORMAT.to_string(),
+            leaf_encoding: SCHEMA.leaf_encoding().iter().map(|s| s.to_string()).collect(),
+            tree: vec![format!("0x{}", "11".repeat(32)), format!("0x{}", hex::encode(leaf))],
+            values: vec![StandardTreeValue {
+                value: claim_to_value(&SCHEMA, claim),
+                tree_index: 1,
+            }],
+        };
+
+        assert!(StandardMerkleTree::load(&dump).is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_duplicate_tree_index() {
+        let claims = vec![sample_claims()[0].clone(), sample_claims()[0].clone()];
+        let tree = StandardMerkleTree::of(SCHEMA, &claims).unwrap();
+        let mut dump = tree.dump();
+        dump.values[1].tree_index = dump.values[0].tree_index;
+
+        assert!(StandardMerkleTree::load(&dump).is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_missing_values() {
+        let tree = StandardMerkleTree::of(SCHEMA, &sample_claims()).unwrap();
+        let mut dump = tree.dump();
+        dump.values.pop();
+
+        assert!(StandardMerkleTree::load(&dump).is_err());
+    }
+}
@@ -91,6 +91,49 @@ async fn main() -> anyhow::Result<()> {
         tracing::warn!("CROWDSALE_ADDRESS not set - Chain Watcher disabled");
     }
 
+    // Index claims paid by cumulative distributors (idle until one is configured)
+    let pool_claims = pool.clone();
+    let rpc_url_claims = rpc_url.clone();
+    tokio::spawn(async move {
+        if let Err(e) = services::chain_watcher::watch_cumulative_claims(&rpc_url_claims, pool_claims).await {
+            tracing::error!("Cumulative claim watcher died: {}", e);
+        }
+    });
+
+    // Mark per-epoch claims as paid from DividendVault events
+    if let Ok(dividend_vault_address) = std::env::var("DIVIDEND_VAULT_ADDRESS") {
+        let pool_dividend_claims = pool.clone();
+        let rpc_url_dividend_claims = rpc_url.clone();
+        tokio::spawn(async move {
+            if let Err(e) = services::chain_watcher::watch_dividend_claims(
+                &rpc_url_dividend_claims,
+                &dividend_vault_address,
+                pool_dividend_claims,
+            ).await {
+                tracing::error!("DividendVault claim watcher died: {}", e);
+            }
+        });
+    } else {
+        tracing::warn!("DIVIDEND_VAULT_ADDRESS not set - DividendVault claim watcher disabled");
+    }
+
+    // Close distributions whose claim window has ended and ledger what was left unclaimed
+    let pool_expiry = pool.clone();
+    tokio::spawn(async move {
+        if let Err(e) = services::claim_expiry::expire_unclaimed_dividends(pool_expiry).await {
+            tracing::error!("Claim expiry job died: {}", e);
+        }
+    });
+
+    // Index RoyaltyToken transfers for balance-at-block queries and dividend snapshots
+    let pool_tokens = pool.clone();
+    let rpc_url_tokens = rpc_url.clone();
+    tokio::spawn(async move {
+        if let Err(e) = services::token_indexer::index_royalty_tokens(&rpc_url_tokens, pool_tokens).await {
+            tracing::error!("Token transfer indexer died: {}", e);
+        }
+    });
+
     // Build the app
     let app = Router::new()
         .route("/health", get(health_check))
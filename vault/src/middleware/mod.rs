@@ -4,72 +4,125 @@
 //! by verifying a shared HMAC-SHA256 token or a Firebase/Google ID token.
 
 use axum::{
-    extract::Request,
-    http::{HeaderMap, StatusCode},
+    async_trait,
+    extract::{FromRequestParts, Request},
+    http::{request::Parts, HeaderMap, StatusCode},
     middleware::Next,
     response::Response,
 };
 use sha2::Sha256;
 use hmac::{Hmac, Mac};
 
-#[allow(dead_code)]
 type HmacSha256 = Hmac<Sha256>;
 
+/// How far a token's timestamp may drift from the Vault's clock, in seconds.
+const MAX_TOKEN_AGE_SECS: i64 = 300;
+
+/// The user or service named in a verified `X-Vault-Auth` token.
+///
+/// Handlers that act on someone's behalf take it as an extractor; requests whose
+/// token names no actor are rejected with `401`.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedActor(pub String);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuthenticatedActor {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Self>().cloned().ok_or_else(|| {
+            tracing::warn!("Request without an actor in its X-Vault-Auth token");
+            StatusCode::UNAUTHORIZED
+        })
+    }
+}
+
 /// Service-to-service auth middleware.
 /// Checks the `X-Vault-Auth` header against a shared secret HMAC.
 /// In production, this would verify a Google OIDC token instead.
-#[allow(dead_code)]
+///
+/// The header is `timestamp:signature`, or `timestamp:actor:signature` for requests
+/// made on someone's behalf. The signature is the hex HMAC-SHA256 of everything before
+/// it, so the actor cannot be changed without the secret. A verified actor is attached
+/// to the request as an [`AuthenticatedActor`].
 pub async fn require_service_auth(
     headers: HeaderMap,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
     let shared_secret = std::env::var("VAULT_SHARED_SECRET")
         .unwrap_or_default();
+    let auth_header = headers.get("x-vault-auth").and_then(|v| v.to_str().ok());
 
     // Skip auth in development if no secret configured
     if shared_secret.is_empty() {
         tracing::warn!("VAULT_SHARED_SECRET not set — auth disabled (dev mode)");
+        // The actor is taken unverified, so lifecycle endpoints stay usable locally
+        if let Some(actor) = auth_header.and_then(parse_token).and_then(|t| t.actor) {
+            request.extensions_mut().insert(AuthenticatedActor(actor.to_string()));
+        }
         return Ok(next.run(request).await);
     }
 
-    let auth_header = headers
-        .get("x-vault-auth")
-        .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| {
-            tracing::warn!("Missing X-Vault-Auth header");
-            StatusCode::UNAUTHORIZED
-        })?;
+    let auth_header = auth_header.ok_or_else(|| {
+        tracing::warn!("Missing X-Vault-Auth header");
+        StatusCode::UNAUTHORIZED
+    })?;
 
-    // Extract timestamp and signature from "timestamp:signature" format
-    let parts: Vec<&str> = auth_header.splitn(2, ':').collect();
-    if parts.len() != 2 {
-        return Err(StatusCode::UNAUTHORIZED);
+    let actor = verify_token(&shared_secret, auth_header, chrono::Utc::now().timestamp())?;
+    if let Some(actor) = actor {
+        request.extensions_mut().insert(AuthenticatedActor(actor));
     }
 
-    let timestamp = parts[0];
-    let provided_sig = parts[1];
+    Ok(next.run(request).await)
+}
+
+/// The parts of an `X-Vault-Auth` header.
+struct AuthToken<'a> {
+    timestamp: &'a str,
+    actor: Option<&'a str>,
+    /// Everything the signature covers.
+    signed: &'a str,
+    signature: &'a str,
+}
+
+/// Split `timestamp:signature` or `timestamp:actor:signature`.
+fn parse_token(header: &str) -> Option<AuthToken<'_>> {
+    let (signed, signature) = header.rsplit_once(':')?;
+    let (timestamp, actor) = match signed.split_once(':') {
+        Some((timestamp, actor)) => (timestamp, Some(actor).filter(|a| !a.trim().is_empty())),
+        None => (signed, None),
+    };
+    Some(AuthToken {
+        timestamp,
+        actor,
+        signed,
+        signature,
+    })
+}
+
+/// Check a token against `secret` at Unix time `now` and return the actor it names.
+fn verify_token(secret: &str, header: &str, now: i64) -> Result<Option<String>, StatusCode> {
+    let token = parse_token(header).ok_or(StatusCode::UNAUTHORIZED)?;
 
     // Verify timestamp is within 5 minutes
-    let ts: i64 = timestamp.parse().map_err(|_| StatusCode::UNAUTHORIZED)?;
-    let now = chrono::Utc::now().timestamp();
-    if (now - ts).abs() > 300 {
+    let ts: i64 = token.timestamp.parse().map_err(|_| StatusCode::UNAUTHORIZED)?;
+    if (now - ts).abs() > MAX_TOKEN_AGE_SECS {
         tracing::warn!("Auth token expired (timestamp drift > 5min)");
         return Err(StatusCode::UNAUTHORIZED);
     }
 
     // Verify HMAC
-    let mut mac = HmacSha256::new_from_slice(shared_secret.as_bytes())
+    let provided = hex::decode(token.signature).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    mac.update(timestamp.as_bytes());
-
-    let expected = hex::encode(mac.finalize().into_bytes());
-    if expected != provided_sig {
+    mac.update(token.signed.as_bytes());
+    if mac.verify_slice(&provided).is_err() {
         tracing::warn!("Invalid auth signature");
         return Err(StatusCode::UNAUTHORIZED);
     }
 
-    Ok(next.run(request).await)
+    Ok(token.actor.map(str::to_string))
 }
 
 /// Health check bypass — no auth required.
@@ -80,3 +133,41 @@ pub async fn health_passthrough(
 ) -> Response {
     next.run(request).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "test-secret";
+    const NOW: i64 = 1_700_000_000;
+
+    fn sign(signed: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(signed.as_bytes());
+        format!("{}:{}", signed, hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn test_token_without_actor() {
+        let token = sign(&NOW.to_string());
+        assert_eq!(verify_token(SECRET, &token, NOW), Ok(None));
+    }
+
+    #[test]
+    fn test_token_names_signed_actor() {
+        let token = sign(&format!("{}:lawyer-1", NOW));
+        assert_eq!(verify_token(SECRET, &token, NOW + 10), Ok(Some("lawyer-1".to_string())));
+
+        // Swapping the actor breaks the signature
+        let forged = token.replacen("lawyer-1", "lawyer-2", 1);
+        assert_eq!(verify_token(SECRET, &forged, NOW), Err(StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_token_rejects_stale_or_malformed() {
+        let token = sign(&format!("{}:ops", NOW));
+        assert_eq!(verify_token(SECRET, &token, NOW + MAX_TOKEN_AGE_SECS + 1), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(verify_token("other-secret", &token, NOW), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(verify_token(SECRET, "garbage", NOW), Err(StatusCode::UNAUTHORIZED));
+    }
+}
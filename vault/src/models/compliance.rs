@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// One row of `compliance_fee_splits`: a share of gross revenue paid before holders.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FeeSplit {
+    pub id: Uuid,
+    pub invention_id: String,
+    /// LAWYER, PLATFORM, INVENTOR or DAO_TREASURY
+    pub recipient_type: String,
+    /// EIP-55 checksummed
+    pub recipient_address: String,
+    /// Percentage of revenue (0-100)
+    pub percentage: Decimal,
+    /// May share its recipient type with other splits.
+    #[serde(default)]
+    pub allow_duplicate_type: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An invention's current fee splits and the version they belong to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeSplitSet {
+    pub invention_id: String,
+    /// 0 until the first change made through the Vault.
+    pub version: i32,
+    pub total_percentage: Decimal,
+    pub splits: Vec<FeeSplit>,
+}
+
+/// How revenue is split before token holders are paid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FeeMode {
+    /// Arizona ABS: `compliance_fee_splits` defines every fee (`fee_sharing_enabled = TRUE`).
+    Abs,
+    /// Platform fee + inventor share from `invention_ledger`; the rest is the investor pool.
+    Standard,
+}
+
+/// A fee taken from revenue, as a percentage, before holders are paid.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FeeShare {
+    pub recipient_type: String,
+    pub recipient_address: String,
+    pub percentage: Decimal,
+}
+
+/// Fee configuration columns of `invention_ledger`.
+#[derive(Debug, Clone, FromRow)]
+pub struct LedgerFeeConfig {
+    pub fee_sharing_enabled: bool,
+    pub compliance_lawyer_id: Option<String>,
+    pub platform_fee_percentage: Option<Decimal>,
+    pub platform_fee_address: Option<String>,
+    pub inventor_share_percentage: Option<Decimal>,
+    pub inventor_wallet_address: Option<String>,
+}
+
+impl LedgerFeeConfig {
+    pub fn fee_mode(&self) -> FeeMode {
+        if self.fee_sharing_enabled {
+            FeeMode::Abs
+        } else {
+            FeeMode::Standard
+        }
+    }
+}
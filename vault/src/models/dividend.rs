@@ -4,14 +4,382 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use crate::crypto::attestation::SignedAttestation;
+use crate::crypto::merkle::{MergedLeaf, StandardLeafSchema};
+use crate::crypto::voucher::SignedVoucher;
+use crate::models::compliance::FeeMode;
+
+/// How leaf amounts relate to earlier distributions of the same invention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DistributionMode {
+    /// Each leaf is the amount for this epoch, claimed with `claimDividend(epoch, ...)`.
+    #[default]
+    PerEpoch,
+    /// Each leaf is the wallet's lifetime total across all cumulative distributions.
+    Cumulative,
+}
+
+impl DistributionMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PerEpoch => "PER_EPOCH",
+            Self::Cumulative => "CUMULATIVE",
+        }
+    }
+}
+
+impl std::str::FromStr for DistributionMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "PER_EPOCH" => Ok(Self::PerEpoch),
+            "CUMULATIVE" => Ok(Self::Cumulative),
+            other => Err(anyhow::anyhow!("Unknown distribution mode '{}'", other)),
+        }
+    }
+}
+
+/// Where a distribution is in its lifecycle. Claims are only offered while `Published`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DistributionStatus {
+    /// Calculated and stored; awaiting approval.
+    #[default]
+    Draft,
+    /// Approved for publication.
+    Approved,
+    /// Claimed by a publisher; the root is being posted on-chain.
+    Publishing,
+    /// Root on-chain (or vouchers released); holders can claim.
+    Published,
+    /// Claim period over.
+    Closed,
+    /// Stopped by the invention's compliance lawyer.
+    Vetoed,
+    /// Withdrawn before publication.
+    Cancelled,
+}
+
+impl DistributionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "DRAFT",
+            Self::Approved => "APPROVED",
+            Self::Publishing => "PUBLISHING",
+            Self::Published => "PUBLISHED",
+            Self::Closed => "CLOSED",
+            Self::Vetoed => "VETOED",
+            Self::Cancelled => "CANCELLED",
+        }
+    }
+}
+
+impl std::str::FromStr for DistributionStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "DRAFT" => Ok(Self::Draft),
+            "APPROVED" => Ok(Self::Approved),
+            "PUBLISHING" => Ok(Self::Publishing),
+            "PUBLISHED" => Ok(Self::Published),
+            "CLOSED" => Ok(Self::Closed),
+            "VETOED" => Ok(Self::Vetoed),
+            "CANCELLED" => Ok(Self::Cancelled),
+            other => Err(anyhow::anyhow!("Unknown distribution status '{}'", other)),
+        }
+    }
+}
+
+/// Result of a lifecycle transition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionStatusChange {
+    pub distribution_id: Uuid,
+    pub from: DistributionStatus,
+    pub to: DistributionStatus,
+}
+
+/// What happens to a claim left unclaimed when its distribution's claim window ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UnclaimedPolicy {
+    /// Held and reported for escheatment.
+    #[default]
+    Escheat,
+}
+
+impl UnclaimedPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Escheat => "ESCHEAT",
+        }
+    }
+}
+
+impl std::str::FromStr for UnclaimedPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ESCHEAT" => Ok(Self::Escheat),
+            other => Err(anyhow::anyhow!("Unknown unclaimed policy '{}'", other)),
+        }
+    }
+}
+
+/// An expired claim recorded in the unclaimed dividend ledger.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UnclaimedDividend {
+    pub claim_id: Uuid,
+    pub distribution_id: Uuid,
+    pub invention_id: String,
+    pub wallet_address: String,
+    pub amount_usdc: Decimal,
+    pub policy: String,
+    pub treasury_address: Option<String>,
+    pub expired_at: DateTime<Utc>,
+    pub applied_distribution_id: Option<Uuid>,
+    pub claimed_after_expiry_tx_hash: Option<String>,
+}
+
+/// An invention's unclaimed dividend ledger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnclaimedReport {
+    pub invention_id: String,
+    pub total_usdc: Decimal,
+    pub entries: Vec<UnclaimedDividend>,
+}
+
+/// How recipients collect their claims.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayoutMethod {
+    /// Merkle root published to DividendVault; claims carry a proof.
+    #[default]
+    Merkle,
+    /// One EIP-712 voucher signed by the Vault per claim.
+    Voucher,
+}
+
+impl PayoutMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Merkle => "MERKLE",
+            Self::Voucher => "VOUCHER",
+        }
+    }
+}
+
+impl std::str::FromStr for PayoutMethod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "MERKLE" => Ok(Self::Merkle),
+            "VOUCHER" => Ok(Self::Voucher),
+            other => Err(anyhow::anyhow!("Unknown payout method '{}'", other)),
+        }
+    }
+}
+
+/// One fee deducted from revenue before holders are paid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeLine {
+    pub recipient_type: String,
+    pub recipient_address: String,
+    pub percentage: Decimal,
+    pub amount_usdc: Decimal,
+}
+
+/// How a distribution's revenue was split between fees and the investor pool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevenueBreakdown {
+    pub fee_mode: FeeMode,
+    pub gross_usdc: Decimal,
+    pub fees: Vec<FeeLine>,
+    /// Revenue left for token holders after fees.
+    pub investor_pool_usdc: Decimal,
+    /// Amounts below the invention's minimum payout, credited to carry-forward balances
+    /// instead of being deposited.
+    #[serde(default)]
+    pub carried_forward_usdc: Decimal,
+    /// Carry-forward balances of earlier distributions paid out by this one.
+    #[serde(default)]
+    pub carry_forward_released_usdc: Decimal,
+}
+
+/// A change a distribution makes to one wallet's carry-forward balance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarryForwardChange {
+    pub wallet_address: String,
+    /// Positive when an amount below the minimum payout is credited, negative when
+    /// the balance is paid out.
+    pub amount_usdc: Decimal,
+}
+
+/// Base units left over after flooring holder shares, paid one each to the
+/// holders with the largest fractional remainders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DustAllocation {
+    pub amount_wei: String,
+    pub recipients: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DividendDistribution {
     pub id: Uuid,
     pub invention_id: String,
     pub total_revenue_usdc: Decimal,
-    pub merkle_root: String,
+    /// `None` for voucher distributions.
+    pub merkle_root: Option<String>,
     pub claim_count: i32,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub status: DistributionStatus,
+    /// DividendVault epoch assigned when the root was published on-chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch: Option<i64>,
+    /// `createDistribution` transaction that published the root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_tx_hash: Option<String>,
+    #[serde(default)]
+    pub payout_method: PayoutMethod,
+    /// Requested voucher expiry as a Unix timestamp; absent for 90 days after publication.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voucher_deadline: Option<i64>,
+    #[serde(default)]
+    pub mode: DistributionMode,
+    /// Block the holder balances were snapshotted at; absent for caller-supplied balances.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_block: Option<i64>,
+    /// Encoding of the tree's leaves; claims must be verified with the same schema.
+    #[serde(default)]
+    pub leaf_schema: StandardLeafSchema,
+    /// Addresses whose claims were summed into a single leaf.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_leaves: Vec<MergedLeaf>,
+    /// Fees and investor pool; absent for imported distributions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revenue_breakdown: Option<RevenueBreakdown>,
+    /// Rounding dust and who received it; absent when every share was exact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dust: Option<DustAllocation>,
+    /// Vault signature over the distribution; absent when no attestation key is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<SignedAttestation>,
+}
+
+/// One page of an invention's distributions, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionPage {
+    pub invention_id: String,
+    /// Distributions matching the filters, across all pages.
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub distributions: Vec<DividendDistribution>,
+}
+
+/// A distribution with its publication details and claim totals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionDetail {
+    #[serde(flatten)]
+    pub distribution: DividendDistribution,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<Utc>>,
+    /// DividendVault the root was published to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dividend_vault_address: Option<String>,
+    pub claims: ClaimTotals,
+}
+
+/// Claim counts and amounts of one distribution, by state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow)]
+pub struct ClaimTotals {
+    pub total_count: i64,
+    pub total_usdc: Decimal,
+    pub claimed_count: i64,
+    pub claimed_usdc: Decimal,
+    /// Not claimed and still within the claim window.
+    pub unclaimed_count: i64,
+    pub unclaimed_usdc: Decimal,
+    /// Not claimed before the claim window ended.
+    pub expired_count: i64,
+    pub expired_usdc: Decimal,
+}
+
+/// Everything a wallet needs to submit `DividendVault.claimDividend` for one claim.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimCalldata {
+    pub claim_id: Uuid,
+    pub wallet_address: String,
+    pub dividend_vault_address: String,
+    pub chain_id: u64,
+    pub epoch: i64,
+    /// Claim amount in USDC base units (6 decimals), as passed to the contract.
+    pub amount_wei: String,
+    pub amount_usdc: Decimal,
+    pub merkle_proof: Vec<String>,
+    /// ABI-encoded `claimDividend(epoch, amount, merkleProof)`, `0x`-prefixed.
+    pub calldata: String,
+}
+
+/// One page of a distribution's claims, by wallet address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimPage {
+    pub distribution_id: Uuid,
+    /// Claims matching the filters, across all pages.
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub claims: Vec<DividendClaim>,
+}
+
+/// What a distribution would pay, from a dry run that stores and signs nothing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionPreview {
+    pub invention_id: String,
+    pub total_revenue_usdc: Decimal,
+    /// Base units to deposit: revenue plus carried-over expired claims and released
+    /// carry-forward balances, less amounts credited to carry-forward balances.
+    pub deposit_wei: String,
+    /// `None` for voucher distributions.
+    pub merkle_root: Option<String>,
+    pub claim_count: i32,
+    pub payout_method: PayoutMethod,
+    pub mode: DistributionMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_block: Option<i64>,
+    pub leaf_schema: StandardLeafSchema,
+    /// One entry per leaf, in tree order.
+    pub recipients: Vec<PreviewRecipient>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_leaves: Vec<MergedLeaf>,
+    pub revenue_breakdown: RevenueBreakdown,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dust: Option<DustAllocation>,
+    /// Carry-forward balances credited or paid out under the invention's minimum payout.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub carry_forward: Vec<CarryForwardChange>,
+}
+
+/// A previewed leaf and why its wallet is paid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewRecipient {
+    pub wallet_address: String,
+    /// Fee recipient types (`LAWYER`, `PLATFORM`, ...), `HOLDER`, `TREASURY` or
+    /// `CARRY_FORWARD`.
+    pub roles: Vec<String>,
+    /// Base units paid by this distribution, dust and released carry-forward balance included.
+    pub amount_wei: String,
+    pub amount_usdc: Decimal,
+    /// Rounding dust included in `amount_wei`.
+    pub dust_wei: String,
+    /// Lifetime total committed to by the leaf (cumulative distributions only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cumulative_amount_wei: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -24,4 +392,19 @@ pub struct DividendClaim {
     pub claimed: bool,
     pub claim_tx_hash: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Leaf position in the distribution's stored tree; the proof is derived from it.
+    #[serde(skip)]
+    pub tree_index: Option<i32>,
+    /// Lifetime total committed to by the leaf (cumulative distributions only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cumulative_amount_usdc: Option<Decimal>,
+    /// DividendVault epoch to pass to `claimDividend` (published Merkle distributions only).
+    #[sqlx(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoch: Option<i64>,
+    /// Signed voucher to redeem on-chain (voucher distributions only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voucher: Option<sqlx::types::Json<SignedVoucher>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claimed_at: Option<DateTime<Utc>>,
 }
@@ -27,7 +27,7 @@ pub struct Investment {
     pub verified_at: Option<DateTime<Utc>>, // Renamed from confirmed_at
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VerifyRequest {
     pub invention_id: String,
     pub wallet_address: String,
@@ -1,2 +1,4 @@
 pub mod investment;
 pub mod dividend;
+pub mod token;
+pub mod compliance;
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// One wallet's RoyaltyToken balance, in base units (18 decimals) as a decimal string.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenHolderBalance {
+    pub wallet_address: String,
+    pub balance_wei: String,
+}
+
+/// All non-zero balances of a token at the end of `block_number`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBalances {
+    pub token_address: String,
+    pub block_number: u64,
+    /// Last block reflected in the index.
+    pub indexed_through: u64,
+    pub holder_count: usize,
+    pub holders: Vec<TokenHolderBalance>,
+}
+
+/// Holder count at the end of a block in which balances changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderCountPoint {
+    pub block_number: u64,
+    pub holder_count: i64,
+}
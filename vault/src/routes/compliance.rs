@@ -0,0 +1,373 @@
+//! Compliance routes.
+//! Manages an invention's ABS fee splits. Every created or updated split is validated
+//! against the resulting set; every change is versioned and audit-logged with
+//! before/after snapshots.
+//! Also lets an invention's compliance lawyer veto a distribution before it is published.
+
+use axum::{
+    extract::{Path, State},
+    routing::{get, post, put},
+    Json, Router,
+};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::AuthenticatedActor;
+use crate::models::compliance::{FeeSplit, FeeSplitSet};
+use crate::models::dividend::{DistributionStatus, DistributionStatusChange};
+use crate::services::distribution_lifecycle;
+use crate::services::fee_splits::validate_fee_split_change;
+
+pub fn router(pool: PgPool) -> Router {
+    Router::new()
+        .route("/fee-splits/:invention_id", get(list_fee_splits).post(create_fee_split))
+        .route(
+            "/fee-splits/:invention_id/:split_id",
+            put(update_fee_split).delete(delete_fee_split),
+        )
+        .route("/veto", post(veto_distribution))
+        .with_state(pool)
+}
+
+#[derive(serde::Deserialize)]
+struct CreateFeeSplitRequest {
+    recipient_type: String,
+    recipient_address: String,
+    percentage: Decimal,
+    /// Allow a second split with the same recipient type.
+    #[serde(default)]
+    allow_duplicate_type: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct UpdateFeeSplitRequest {
+    recipient_type: Option<String>,
+    recipient_address: Option<String>,
+    percentage: Option<Decimal>,
+    allow_duplicate_type: Option<bool>,
+}
+
+#[derive(serde::Deserialize)]
+struct VetoRequest {
+    distribution_id: Uuid,
+    reason: String,
+}
+
+/// A single change to an invention's split set.
+enum FeeSplitChange {
+    Create(FeeSplit),
+    Update(Uuid, UpdateFeeSplitRequest),
+    Delete(Uuid),
+}
+
+impl FeeSplitChange {
+    fn action(&self) -> &'static str {
+        match self {
+            Self::Create(_) => "CREATE",
+            Self::Update(..) => "UPDATE",
+            Self::Delete(_) => "DELETE",
+        }
+    }
+
+    fn split_id(&self) -> Uuid {
+        match self {
+            Self::Create(split) => split.id,
+            Self::Update(id, _) | Self::Delete(id) => *id,
+        }
+    }
+
+    /// Apply the change to the current split set.
+    fn apply(self, splits: &mut Vec<FeeSplit>) -> Result<(), axum::http::StatusCode> {
+        match self {
+            Self::Create(split) => splits.push(split),
+            Self::Update(id, req) => {
+                let split = splits
+                    .iter_mut()
+                    .find(|s| s.id == id)
+                    .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+                if let Some(recipient_type) = req.recipient_type {
+                    split.recipient_type = recipient_type;
+                }
+                if let Some(recipient_address) = req.recipient_address {
+                    split.recipient_address = recipient_address;
+                }
+                if let Some(percentage) = req.percentage {
+                    split.percentage = percentage;
+                }
+                if let Some(allow_duplicate_type) = req.allow_duplicate_type {
+                    split.allow_duplicate_type = allow_duplicate_type;
+                }
+            }
+            Self::Delete(id) => {
+                let before = splits.len();
+                splits.retain(|s| s.id != id);
+                if splits.len() == before {
+                    return Err(axum::http::StatusCode::NOT_FOUND);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// GET /api/v1/vault/compliance/fee-splits/:invention_id
+/// Current fee splits of an invention.
+async fn list_fee_splits(
+    State(pool): State<PgPool>,
+    Path(invention_id): Path<String>,
+) -> Result<Json<FeeSplitSet>, axum::http::StatusCode> {
+    let exists: Option<String> = sqlx::query_scalar(
+        "SELECT invention_id FROM invention_ledger WHERE invention_id = $1",
+    )
+    .bind(&invention_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    if exists.is_none() {
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
+
+    let splits = sqlx::query_as::<_, FeeSplit>(
+        "SELECT id, invention_id, recipient_type, recipient_address, percentage, allow_duplicate_type, created_at
+         FROM compliance_fee_splits WHERE invention_id = $1 ORDER BY created_at, id",
+    )
+    .bind(&invention_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch fee splits for {}: {}", invention_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let version: i32 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(version), 0) FROM compliance_fee_split_versions WHERE invention_id = $1",
+    )
+    .bind(&invention_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(FeeSplitSet {
+        total_percentage: splits.iter().map(|s| s.percentage).sum(),
+        invention_id,
+        version,
+        splits,
+    }))
+}
+
+/// POST /api/v1/vault/compliance/fee-splits/:invention_id
+/// Add a fee split.
+async fn create_fee_split(
+    State(pool): State<PgPool>,
+    Path(invention_id): Path<String>,
+    AuthenticatedActor(actor): AuthenticatedActor,
+    Json(req): Json<CreateFeeSplitRequest>,
+) -> Result<Json<FeeSplitSet>, axum::http::StatusCode> {
+    let split = FeeSplit {
+        id: Uuid::new_v4(),
+        invention_id: invention_id.clone(),
+        recipient_type: req.recipient_type,
+        recipient_address: req.recipient_address,
+        percentage: req.percentage,
+        allow_duplicate_type: req.allow_duplicate_type,
+        created_at: chrono::Utc::now(),
+    };
+
+    change_fee_splits(&pool, &invention_id, &actor, FeeSplitChange::Create(split)).await
+}
+
+/// PUT /api/v1/vault/compliance/fee-splits/:invention_id/:split_id
+/// Change a fee split's recipient or percentage.
+async fn update_fee_split(
+    State(pool): State<PgPool>,
+    Path((invention_id, split_id)): Path<(String, Uuid)>,
+    AuthenticatedActor(actor): AuthenticatedActor,
+    Json(req): Json<UpdateFeeSplitRequest>,
+) -> Result<Json<FeeSplitSet>, axum::http::StatusCode> {
+    change_fee_splits(&pool, &invention_id, &actor, FeeSplitChange::Update(split_id, req)).await
+}
+
+/// DELETE /api/v1/vault/compliance/fee-splits/:invention_id/:split_id
+/// Remove a fee split.
+async fn delete_fee_split(
+    State(pool): State<PgPool>,
+    Path((invention_id, split_id)): Path<(String, Uuid)>,
+    AuthenticatedActor(actor): AuthenticatedActor,
+) -> Result<Json<FeeSplitSet>, axum::http::StatusCode> {
+    change_fee_splits(&pool, &invention_id, &actor, FeeSplitChange::Delete(split_id)).await
+}
+
+/// Apply one change in a transaction: lock the invention, validate the created or updated
+/// split, write the resulting set, and record a new version plus a `FEE_SPLIT_CHANGE`
+/// audit entry. Deletes are never rejected, so invalid rows can always be removed.
+async fn change_fee_splits(
+    pool: &PgPool,
+    invention_id: &str,
+    actor: &str,
+    change: FeeSplitChange,
+) -> Result<Json<FeeSplitSet>, axum::http::StatusCode> {
+    let db_error = |e: sqlx::Error| {
+        tracing::error!("Fee split change for {} failed: {}", invention_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+
+    let mut tx = pool.begin().await.map_err(db_error)?;
+
+    // Serializes concurrent changes to the same invention's splits
+    let exists: Option<String> = sqlx::query_scalar(
+        "SELECT invention_id FROM invention_ledger WHERE invention_id = $1 FOR UPDATE",
+    )
+    .bind(invention_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    if exists.is_none() {
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
+
+    let before = sqlx::query_as::<_, FeeSplit>(
+        "SELECT id, invention_id, recipient_type, recipient_address, percentage, allow_duplicate_type, created_at
+         FROM compliance_fee_splits WHERE invention_id = $1 ORDER BY created_at, id",
+    )
+    .bind(invention_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    let action = change.action();
+    let split_id = change.split_id();
+    let mut after = before.clone();
+    change.apply(&mut after)?;
+
+    if let Some(changed) = after.iter().find(|s| s.id == split_id) {
+        validate_fee_split_change(&after, changed).map_err(|e| {
+            tracing::warn!("Rejected fee split change for {}: {}", invention_id, e);
+            axum::http::StatusCode::BAD_REQUEST
+        })?;
+    }
+
+    // Rewrite the set; ids and creation times carry over for unchanged splits
+    sqlx::query("DELETE FROM compliance_fee_splits WHERE invention_id = $1")
+        .bind(invention_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+    for split in &after {
+        sqlx::query(
+            "INSERT INTO compliance_fee_splits (id, invention_id, recipient_type, recipient_address, percentage, allow_duplicate_type, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(split.id)
+        .bind(invention_id)
+        .bind(&split.recipient_type)
+        .bind(&split.recipient_address)
+        .bind(split.percentage)
+        .bind(split.allow_duplicate_type)
+        .bind(split.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    }
+
+    let version: i32 = sqlx::query_scalar(
+        "INSERT INTO compliance_fee_split_versions (invention_id, version, splits, actor, created_at)
+         SELECT $1, COALESCE(MAX(version), 0) + 1, $2, $3, NOW()
+         FROM compliance_fee_split_versions WHERE invention_id = $1
+         RETURNING version",
+    )
+    .bind(invention_id)
+    .bind(sqlx::types::Json(&after))
+    .bind(actor)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    let audit_payload = serde_json::json!({
+        "action": action,
+        "split_id": split_id,
+        "version": version,
+        "before": before,
+        "after": after,
+    });
+
+    sqlx::query(
+        "INSERT INTO audit_logs (event_type, actor, target_resource, payload, created_at) VALUES ($1, $2, $3, $4, NOW())"
+    )
+    .bind("FEE_SPLIT_CHANGE")
+    .bind(actor)
+    .bind(invention_id)
+    .bind(&audit_payload)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    tx.commit().await.map_err(db_error)?;
+
+    tracing::info!(
+        "Fee splits for {} changed by {}: {} {} (version {})",
+        invention_id,
+        actor,
+        action,
+        split_id,
+        version
+    );
+
+    Ok(Json(FeeSplitSet {
+        invention_id: invention_id.to_string(),
+        version,
+        total_percentage: after.iter().map(|s| s.percentage).sum(),
+        splits: after,
+    }))
+}
+
+/// POST /api/v1/vault/compliance/veto
+/// Veto a draft or approved distribution. Only the invention's compliance lawyer may veto.
+async fn veto_distribution(
+    State(pool): State<PgPool>,
+    AuthenticatedActor(actor): AuthenticatedActor,
+    Json(req): Json<VetoRequest>,
+) -> Result<Json<DistributionStatusChange>, axum::http::StatusCode> {
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    let lawyer: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT l.compliance_lawyer_id FROM dividend_distributions d
+         JOIN invention_ledger l ON l.invention_id = d.invention_id
+         WHERE d.id = $1",
+    )
+    .bind(req.distribution_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load compliance lawyer for {}: {}", req.distribution_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let lawyer = lawyer.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    if lawyer.as_deref() != Some(actor.as_str()) {
+        tracing::warn!(
+            "Veto of {} by {} refused: not the invention's compliance lawyer",
+            req.distribution_id,
+            actor
+        );
+        return Err(axum::http::StatusCode::FORBIDDEN);
+    }
+
+    let to = DistributionStatus::Vetoed;
+    let from = distribution_lifecycle::transition(&pool, req.distribution_id, to, &actor, Some(reason))
+        .await
+        .map_err(|e| {
+            tracing::warn!("Veto of {} refused: {}", req.distribution_id, e);
+            axum::http::StatusCode::from(e)
+        })?;
+
+    Ok(Json(DistributionStatusChange {
+        distribution_id: req.distribution_id,
+        from,
+        to,
+    }))
+}
@@ -2,34 +2,106 @@
 //! Handles calculating and distributing royalty payments to token holders.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderName},
+    response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
 use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
 
-use crate::crypto::merkle::{build_merkle_tree, ClaimLeaf};
-use crate::models::dividend::{DividendClaim, DividendDistribution};
+use crate::crypto::attestation::{signer_from_env, DistributionAttestation, SignedAttestation};
+use crate::crypto::merkle::{
+    canonicalize_claims, multiproof_layout, verify_multiproof, verify_proof, ClaimLeaf, MergedLeaf, MerkleTreeBuilder,
+    StandardLeafSchema,
+};
+use crate::crypto::standard_tree::{proof_path, StandardMerkleTree, StandardTreeDump};
+use crate::crypto::voucher::{ClaimVoucher, SignedVoucher};
+use crate::middleware::AuthenticatedActor;
+use crate::models::compliance::{FeeMode, FeeShare, LedgerFeeConfig};
+use crate::models::dividend::{
+    CarryForwardChange, ClaimCalldata, ClaimPage, ClaimTotals, DistributionDetail, DistributionMode, DistributionPage, DistributionPreview,
+    DistributionStatus, DistributionStatusChange, DividendClaim, UnclaimedDividend, UnclaimedPolicy,
+    UnclaimedReport, DividendDistribution, DustAllocation, FeeLine, PayoutMethod, PreviewRecipient,
+    RevenueBreakdown,
+};
+use crate::services::chain_watcher;
+use crate::services::claim_expiry;
+use crate::services::distribution_lifecycle;
+use crate::services::distribution_publisher::{self, PublisherConfig};
+use crate::services::distribution_store::{self, DistributionRecord, NewClaim};
+use crate::services::fee_splits::{abs_fee_shares, standard_fee_shares};
+use crate::services::holder_snapshot::snapshot_holders_from_index;
+use crate::services::idempotency::{run_idempotent, ClaimedKey};
+use crate::services::token_calculator::{allocate_largest_remainder, apply_minimum_payout, Payout};
+use crate::services::token_indexer::TOKEN_INDEX_CONFIRMATIONS;
+
+/// Claims buffered between the database cursor and the tree builder during a backfill.
+const BACKFILL_CHANNEL_CAPACITY: usize = 4_096;
+
+/// Largest distribution that may be paid out with vouchers instead of a Merkle tree.
+const MAX_VOUCHER_CLAIMS: usize = 32;
+
+/// Default voucher lifetime from publication when the request does not set a deadline.
+const VOUCHER_VALIDITY: chrono::Duration = chrono::Duration::days(90);
+
+/// Least time a voucher deadline must leave when funding starts, so the epoch is
+/// confirmed and the vouchers signed well before they expire.
+const VOUCHER_PUBLISH_MARGIN: chrono::Duration = chrono::Duration::hours(1);
+
+/// Attested Merkle root of voucher distributions, which have no tree.
+const NO_MERKLE_ROOT: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
 
 pub fn router(pool: PgPool) -> Router {
     Router::new()
         .route("/distribute/:invention_id", post(distribute_dividends))
+        .route("/distribute/:invention_id/preview", post(preview_distribution))
         .route("/claims/:wallet_address", get(get_claimable))
+        .route("/claims/:wallet_address/:claim_id/calldata", get(get_claim_calldata))
+        .route("/verify-claim", post(verify_claim))
+        .route("/distributions/:id/standard-tree", get(export_standard_tree))
+        .route("/distributions/:id/tree", get(download_tree))
+        .route("/import/:invention_id", post(import_standard_tree))
+        .route("/multiproof", post(get_multiproofs))
+        .route("/verify-attestation", post(verify_attestation))
+        .route("/vouchers/:claim_id/redeem", post(redeem_voucher))
+        .route("/distributions/:id/approve", post(approve_distribution))
+        .route("/distributions/:id/publish", post(publish_distribution))
+        .route("/distributions/:id/close", post(close_distribution))
+        .route("/distributions/:id/cancel", post(cancel_distribution))
+        .route("/unclaimed/:invention_id", get(get_unclaimed))
+        .route("/by-invention/:invention_id", get(list_distributions))
+        .route("/distributions/:id", get(get_distribution))
+        .route("/distributions/:id/claims", get(list_distribution_claims))
         .with_state(pool)
 }
 
 /// Request body for dividend distribution.
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct DistributeRequest {
     revenue_usdc: rust_decimal::Decimal,
-    /// List of token holders with their balances.
-    /// In production this would be fetched from the RoyaltyToken contract,
-    /// but for MVP the caller provides this data.
+    /// Block at which holder balances are read from the invention's RoyaltyToken.
+    /// Required when `invention_ledger.royalty_token_address` is set.
+    snapshot_block: Option<u64>,
+    /// Caller-supplied holder balances, accepted only for inventions without a RoyaltyToken.
+    #[serde(default)]
     holders: Vec<HolderBalance>,
+    /// Leaf encoding expected by the target DividendVault. Defaults to `(address, uint256)`.
+    #[serde(default)]
+    leaf_schema: StandardLeafSchema,
+    /// `CUMULATIVE` builds leaves from each wallet's lifetime total for the invention.
+    #[serde(default)]
+    mode: DistributionMode,
+    /// `VOUCHER` signs one EIP-712 voucher per claim instead of building a Merkle tree.
+    #[serde(default)]
+    payout_method: PayoutMethod,
+    /// Voucher expiry as a Unix timestamp; defaults to 90 days after publication.
+    voucher_deadline: Option<i64>,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct HolderBalance {
     wallet_address: String,
     token_balance: rust_decimal::Decimal,
@@ -37,17 +109,264 @@ struct HolderBalance {
 
 /// POST /api/v1/vault/dividends/distribute/:invention_id
 /// Calculate and create dividend distribution for an invention's token holders.
-/// Called when licensing revenue is received.
+/// Called when licensing revenue is received. Retries with the same
+/// `Idempotency-Key` get the original distribution back.
 async fn distribute_dividends(
     State(pool): State<PgPool>,
     Path(invention_id): Path<String>,
+    headers: HeaderMap,
     Json(payload): Json<DistributeRequest>,
 ) -> Result<Json<DividendDistribution>, axum::http::StatusCode> {
+    run_idempotent(
+        &pool,
+        &headers,
+        "dividends.distribute",
+        &(&invention_id, &payload),
+        |key| create_distribution(&pool, &invention_id, &payload, key),
+    )
+    .await
+    .map(Json)
+}
+
+/// Calculate, sign and store a distribution. The response is stored under
+/// `idempotency_key` in the same transaction as the distribution.
+async fn create_distribution(
+    pool: &PgPool,
+    invention_id: &str,
+    payload: &DistributeRequest,
+    idempotency_key: Option<ClaimedKey>,
+) -> Result<DividendDistribution, axum::http::StatusCode> {
+    use rust_decimal::prelude::*;
+    let revenue_usdc = payload.revenue_usdc;
+
+    let plan = plan_distribution(pool, invention_id, payload).await?;
+    let merkle_leaves = &plan.leaves;
+    let merkle_root = plan.tree.as_ref().map(|t| t.root());
+
+    let distribution_id = Uuid::new_v4();
+
+    let audit_payload = serde_json::json!({
+        "total_revenue": revenue_usdc,
+        "net_revenue": plan.net_revenue.to_string(),
+        "revenue_breakdown": plan.revenue_breakdown,
+        "fee_count": plan.fee_count,
+        "claim_count_total": merkle_leaves.len(),
+        "merged_leaves": plan.merged_leaves,
+        "leaf_schema": payload.leaf_schema,
+        "mode": payload.mode,
+        "payout_method": payload.payout_method,
+        "royalty_token_address": plan.royalty_token.map(|t| format!("{:#x}", t)),
+        "snapshot_block": payload.snapshot_block,
+        "dust": plan.dust,
+        "carry_forward": plan.carry_forward,
+    });
+
+    // Sign the root as produced here, before anyone else handles it
+    let attestation = attest_distribution(
+        invention_id,
+        distribution_id,
+        merkle_root.as_deref().unwrap_or(NO_MERKLE_ROOT),
+        plan.deposit_usdc,
+        merkle_leaves.len(),
+    )?;
+
+    // Claims keep their leaf position; vouchers are only signed on publication
+    let mut claims = Vec::with_capacity(merkle_leaves.len());
+    for (i, leaf) in merkle_leaves.iter().enumerate() {
+        let tree_index = match &plan.tree {
+            Some(tree) => Some(tree.tree_index(i).ok_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR)? as i32),
+            None => None,
+        };
+        // Convert wei-amount back to Decimal for DB storage (6 decimal places)
+        let added = plan.added_wei.get(&leaf.wallet_address).map(String::as_str).unwrap_or("0");
+        let amount_usdc = Decimal::from_str(added).unwrap_or_default() / Decimal::from(1_000_000);
+        let cumulative_amount_usdc = match payload.mode {
+            DistributionMode::PerEpoch => None,
+            DistributionMode::Cumulative => {
+                Some(Decimal::from_str(&leaf.amount_wei).unwrap_or_default() / Decimal::from(1_000_000))
+            }
+        };
+
+        claims.push(NewClaim {
+            id: Uuid::new_v4(),
+            wallet_address: leaf.wallet_address.clone(),
+            amount_usdc,
+            cumulative_amount_usdc,
+            tree_index,
+        });
+    }
+
+    let distribution = DividendDistribution {
+        id: distribution_id,
+        invention_id: invention_id.to_string(),
+        total_revenue_usdc: revenue_usdc,
+        merkle_root,
+        claim_count: merkle_leaves.len() as i32,
+        created_at: chrono::Utc::now(),
+        status: DistributionStatus::Draft,
+        epoch: None,
+        publish_tx_hash: None,
+        payout_method: payload.payout_method,
+        voucher_deadline: payload.voucher_deadline.filter(|_| payload.payout_method == PayoutMethod::Voucher),
+        mode: payload.mode,
+        snapshot_block: payload.snapshot_block.map(|b| b as i64),
+        leaf_schema: payload.leaf_schema,
+        merged_leaves: plan.merged_leaves,
+        revenue_breakdown: Some(plan.revenue_breakdown),
+        dust: plan.dust,
+        attestation,
+    };
+
+    // Audit entry, distribution, tree, claims and carry-forward balances
+    // commit together or not at all
+    let record = DistributionRecord {
+        distribution: &distribution,
+        tree: plan.tree.as_ref(),
+        claims,
+        carry_forward: plan.carry_forward,
+        audit_event: "DIVIDEND_DISTRIBUTION",
+        actor: "system",
+        audit_payload,
+        idempotency_key: idempotency_key.as_ref(),
+    };
+    distribution_store::store_distribution(pool, &record).await.map_err(|e| {
+        tracing::error!("Failed to store distribution {} for {}: {}", distribution_id, invention_id, e);
+        axum::http::StatusCode::from(e)
+    })?;
+
+    tracing::info!(
+        "Distribution {} created: method={}, root={}, claims={}",
+        distribution_id,
+        payload.payout_method.as_str(),
+        distribution.merkle_root.as_deref().unwrap_or("-"),
+        merkle_leaves.len()
+    );
+
+    Ok(distribution)
+}
+
+/// POST /api/v1/vault/dividends/distribute/:invention_id/preview
+/// Dry run of `distribute`: the same calculation and Merkle root, with a per-recipient
+/// breakdown. Nothing is stored, audit-logged or signed, and carried-over expired
+/// claims and carry-forward balances stay as they are.
+async fn preview_distribution(
+    State(pool): State<PgPool>,
+    Path(invention_id): Path<String>,
+    Json(payload): Json<DistributeRequest>,
+) -> Result<Json<DistributionPreview>, axum::http::StatusCode> {
+    use rust_decimal::Decimal;
+
+    let plan = plan_distribution(&pool, &invention_id, &payload).await?;
+
+    let recipients = plan
+        .leaves
+        .iter()
+        .map(|leaf| {
+            let added = plan.added_wei.get(&leaf.wallet_address).cloned().unwrap_or_else(|| "0".to_string());
+            let amount_usdc = added
+                .parse::<Decimal>()
+                .map(|wei| wei / Decimal::from(1_000_000))
+                .unwrap_or_default();
+            PreviewRecipient {
+                wallet_address: leaf.wallet_address.clone(),
+                roles: plan.roles.get(&leaf.wallet_address).cloned().unwrap_or_default(),
+                amount_wei: added,
+                amount_usdc,
+                dust_wei: plan.dust_by_wallet.get(&leaf.wallet_address).copied().unwrap_or(0).to_string(),
+                cumulative_amount_wei: (payload.mode == DistributionMode::Cumulative)
+                    .then(|| leaf.amount_wei.clone()),
+            }
+        })
+        .collect();
+
+    tracing::info!(
+        "Previewed distribution for {}: {} claims, root={}",
+        invention_id,
+        plan.leaves.len(),
+        plan.tree.as_ref().map(|t| t.root()).as_deref().unwrap_or("-")
+    );
+
+    Ok(Json(DistributionPreview {
+        invention_id,
+        total_revenue_usdc: payload.revenue_usdc,
+        deposit_wei: plan.deposit_wei.to_string(),
+        merkle_root: plan.tree.as_ref().map(|t| t.root()),
+        claim_count: plan.leaves.len() as i32,
+        payout_method: payload.payout_method,
+        mode: payload.mode,
+        snapshot_block: payload.snapshot_block.map(|b| b as i64),
+        leaf_schema: payload.leaf_schema,
+        recipients,
+        merged_leaves: plan.merged_leaves,
+        revenue_breakdown: plan.revenue_breakdown,
+        dust: plan.dust,
+        carry_forward: plan.carry_forward,
+    }))
+}
+
+/// A distribution as calculated from a request, before anything is signed or stored.
+struct DistributionPlan {
+    fee_count: usize,
+    net_revenue: rust_decimal::Decimal,
+    /// Base units DividendVault receives: revenue plus carried-over expired claims and
+    /// released carry-forward balances, less amounts carried forward.
+    deposit_wei: u128,
+    deposit_usdc: rust_decimal::Decimal,
+    revenue_breakdown: RevenueBreakdown,
+    royalty_token: Option<ethers::types::Address>,
+    dust: Option<DustAllocation>,
+    /// Dust base units received by each holder, keyed by canonical address.
+    dust_by_wallet: HashMap<String, u128>,
+    /// Why each canonical address is paid: fee recipient types, `HOLDER` or `CARRY_FORWARD`.
+    roles: HashMap<String, Vec<String>>,
+    merged_leaves: Vec<MergedLeaf>,
+    /// Amount each wallet gains from this distribution, keyed by canonical address.
+    added_wei: HashMap<String, String>,
+    /// Final leaves: this distribution's amounts, or lifetime totals in cumulative mode.
+    leaves: Vec<ClaimLeaf>,
+    /// `None` for voucher distributions.
+    tree: Option<StandardMerkleTree>,
+    /// Carry-forward balances credited or paid out under the minimum payout.
+    carry_forward: Vec<CarryForwardChange>,
+}
+
+/// Run the full distribution calculation for `payload` without writing anything:
+/// fee deductions, carried-over expired claims, holder shares and rounding dust,
+/// leaf canonicalization, the minimum payout, cumulative totals and the Merkle tree.
+async fn plan_distribution(
+    pool: &PgPool,
+    invention_id: &str,
+    payload: &DistributeRequest,
+) -> Result<DistributionPlan, axum::http::StatusCode> {
     use rust_decimal::prelude::*;
     let revenue_usdc = payload.revenue_usdc;
-    let holders = &payload.holders;
 
-    if holders.is_empty() || revenue_usdc <= Decimal::ZERO {
+    if revenue_usdc <= Decimal::ZERO {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    // Vouchers pay fixed amounts per distribution; there are no leaves to encode or accumulate
+    if payload.payout_method == PayoutMethod::Voucher
+        && (payload.mode != DistributionMode::PerEpoch
+            || payload.leaf_schema != StandardLeafSchema::default())
+    {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    // The on-chain epoch is only assigned at publish time, so a caller-chosen one
+    // could never match it
+    if matches!(payload.leaf_schema, StandardLeafSchema::EpochAddressAmount { .. }) {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    // 0. Fees: ABS fee splits or the standard platform fee + inventor share
+    let (fee_mode, fee_splits) = resolve_fee_shares(pool, invention_id).await?;
+
+    let royalty_token = fetch_royalty_token(pool, invention_id).await?;
+    let snapshot_holders = resolve_holders(pool, royalty_token, payload.snapshot_block, &payload.holders).await?;
+    let holders = snapshot_holders.as_ref().unwrap_or(&payload.holders);
+
+    if holders.is_empty() {
         return Err(axum::http::StatusCode::BAD_REQUEST);
     }
 
@@ -58,88 +377,80 @@ async fn distribute_dividends(
         holders.len()
     );
 
-    // 0. ABS Compliance: Fetch fee splits
-    // Check if there are any mandated fee splits (Lawyer, Platform, etc.)
-    #[derive(sqlx::FromRow)]
-    #[allow(dead_code)]
-    struct FeeSplit {
-        recipient_type: String,
-        recipient_address: String,
-        percentage: rust_decimal::Decimal,
+    // Work in USDC base units (6 decimals) so leaf amounts sum exactly to the deposit
+    let deposit_scaled = revenue_usdc * Decimal::from(1_000_000);
+    if !deposit_scaled.fract().is_zero() {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
     }
+    let deposit_wei = deposit_scaled
+        .to_u128()
+        .ok_or(axum::http::StatusCode::BAD_REQUEST)?;
 
-    let fee_splits = sqlx::query_as::<_, FeeSplit>(
-        "SELECT recipient_type, recipient_address, percentage FROM compliance_fee_splits WHERE invention_id = $1"
-    )
-    .bind(&invention_id)
-    .fetch_all(&pool)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch compliance fee splits: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
-    // use rust_decimal::prelude::*; // Already imported above
-    let mut net_revenue = revenue_usdc;
+    let mut net_wei = deposit_wei;
     let mut claims_data: Vec<(String, String)> = Vec::new(); // (address, amount_wei)
+    let mut fee_lines = Vec::new();
+    let mut roles: HashMap<String, Vec<String>> = HashMap::new();
 
-    // Calculate and deduct fees
+    // Calculate and deduct fees. Fees round down; the remainder stays with holders.
     for split in &fee_splits {
         if split.percentage > Decimal::ZERO {
-            let fee_amount = (revenue_usdc * split.percentage) / Decimal::from(100);
-            net_revenue -= fee_amount;
+            let fee_wei = (Decimal::from(deposit_wei) * split.percentage / Decimal::from(100))
+                .floor()
+                .to_u128()
+                .ok_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+            // Fee splits above 100% would leave holders a negative amount
+            net_wei = net_wei
+                .checked_sub(fee_wei)
+                .ok_or(axum::http::StatusCode::BAD_REQUEST)?;
 
-            // Format to 6 decimals then scale to wei-like integer
-            let amount_wei_decimal = fee_amount * Decimal::from(1_000_000);
-            let amount_wei = amount_wei_decimal.round().to_string();
+            tracing::info!("{:?} Fee Split: {} base units to {} ({:?}%)", fee_mode, fee_wei, split.recipient_address, split.percentage);
 
-            tracing::info!("ABS Fee Split: {} to {} ({:?}%)", fee_amount, split.recipient_address, split.percentage);
-
-            claims_data.push((split.recipient_address.clone(), amount_wei));
+            claims_data.push((split.recipient_address.clone(), fee_wei.to_string()));
+            add_role(&mut roles, &split.recipient_address, &split.recipient_type);
+            fee_lines.push(FeeLine {
+                recipient_type: split.recipient_type.clone(),
+                recipient_address: split.recipient_address.clone(),
+                percentage: split.percentage,
+                amount_usdc: Decimal::from(fee_wei) / Decimal::from(1_000_000),
+            });
         }
     }
 
-    // AUDIT LOGGING
-    let audit_payload = serde_json::json!({
-        "total_revenue": revenue_usdc,
-        "net_revenue": net_revenue.to_string(),
-        "fee_count": fee_splits.len(),
-        "claim_count_total": holders.len() + fee_splits.len(),
-    });
-
-    sqlx::query(
-        "INSERT INTO audit_logs (event_type, actor, target_resource, payload, created_at) VALUES ($1, $2, $3, $4, NOW())"
-    )
-    .bind("DIVIDEND_DISTRIBUTION")
-    .bind("system")
-    .bind(&invention_id)
-    .bind(&audit_payload)
-    .execute(&pool)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to write audit log: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let net_revenue = Decimal::from(net_wei) / Decimal::from(1_000_000);
 
     // 1. Calculate total token supply from holder balances
+    let holders: Vec<&HolderBalance> = holders
+        .iter()
+        .filter(|h| h.token_balance > Decimal::ZERO)
+        .collect();
     let total_supply: Decimal = holders.iter().map(|h| h.token_balance).sum();
     if total_supply <= Decimal::ZERO {
         return Err(axum::http::StatusCode::BAD_REQUEST);
     }
 
-    // 2. Calculate each holder's share of NET revenue and build Merkle tree leaves
-
-    for h in holders {
-        if h.token_balance > Decimal::ZERO {
-            // Share calculation using Decimal
-            let share = (h.token_balance / total_supply) * net_revenue;
+    // 2. Split NET revenue across holders by balance (largest remainder, exact sum)
+    let weights: Vec<Decimal> = holders.iter().map(|h| h.token_balance).collect();
+    let allocation = allocate_largest_remainder(net_wei, &weights).ok_or_else(|| {
+        tracing::warn!("Cannot allocate {} base units across {} holders", net_wei, holders.len());
+        axum::http::StatusCode::BAD_REQUEST
+    })?;
 
-            // Convert to integer string for Merkle tree (USDC 6 decimals -> Wei-like)
-            let amount_wei_decimal = share * Decimal::from(1_000_000);
-            let amount_wei = amount_wei_decimal.round().to_string();
+    for (h, amount_wei) in holders.iter().zip(&allocation.amounts) {
+        claims_data.push((h.wallet_address.clone(), amount_wei.to_string()));
+        add_role(&mut roles, &h.wallet_address, "HOLDER");
+    }
 
-            claims_data.push((h.wallet_address.clone(), amount_wei));
-        }
+    let dust = (allocation.dust > 0).then(|| DustAllocation {
+        amount_wei: allocation.dust.to_string(),
+        recipients: allocation
+            .dust_recipients
+            .iter()
+            .map(|&i| holders[i].wallet_address.to_lowercase())
+            .collect(),
+    });
+    let mut dust_by_wallet: HashMap<String, u128> = HashMap::new();
+    for &i in &allocation.dust_recipients {
+        *dust_by_wallet.entry(holders[i].wallet_address.to_lowercase()).or_default() += 1;
     }
 
     let merkle_leaves: Vec<ClaimLeaf> = claims_data
@@ -150,89 +461,504 @@ async fn distribute_dividends(
         })
         .collect();
 
-    // 3. Build Merkle tree
-    let (merkle_root, proofs) = build_merkle_tree(&merkle_leaves).map_err(|e| {
-        tracing::error!("Failed to build merkle tree: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    // A wallet that is both a fee recipient and a holder gets one summed leaf,
+    // since DividendVault only lets each address claim once per epoch.
+    let (merkle_leaves, merged_leaves) = canonicalize_claims(&merkle_leaves).map_err(|e| {
+        tracing::warn!("Invalid claim data for invention {}: {}", invention_id, e);
+        axum::http::StatusCode::BAD_REQUEST
     })?;
 
-    let distribution_id = Uuid::new_v4();
+    for merged in &merged_leaves {
+        tracing::warn!(
+            "Merged {} claims for {} into one leaf ({} total)",
+            merged.amounts_wei.len(),
+            merged.wallet_address,
+            merged.total_wei
+        );
+    }
 
-    // 4. Store distribution in PostgreSQL
-    sqlx::query(
+    // Amounts below the invention's minimum payout wait in carry-forward balances.
+    // Cumulative leaves already accumulate until the wallet claims.
+    let mut carry_forward = Vec::new();
+    let mut carried_forward_wei = 0u128;
+    let mut released_wei = 0u128;
+    let merkle_leaves = match (payload.mode, fetch_min_payout(pool, invention_id).await?) {
+        (DistributionMode::PerEpoch, Some(minimum_wei)) => {
+            let balances = fetch_carry_forward_balances(pool, invention_id).await?;
+
+            // (amount, balance) per canonical address, in canonical order
+            let mut wallets: BTreeMap<String, (u128, u128)> = BTreeMap::new();
+            for leaf in &merkle_leaves {
+                let amount_wei = leaf
+                    .amount_wei
+                    .parse::<u128>()
+                    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+                wallets.entry(leaf.wallet_address.clone()).or_default().0 = amount_wei;
+            }
+            for (wallet_address, balance_wei) in balances {
+                wallets.entry(wallet_address).or_default().1 = balance_wei;
+            }
+
+            let mut paid = Vec::with_capacity(wallets.len());
+            for (wallet_address, (amount_wei, balance_wei)) in wallets {
+                let payout = apply_minimum_payout(amount_wei, balance_wei, minimum_wei)
+                    .ok_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+                match payout {
+                    Payout::Pay { amount, released } => {
+                        if released > 0 {
+                            released_wei += released;
+                            add_role(&mut roles, &wallet_address, "CARRY_FORWARD");
+                            carry_forward.push(CarryForwardChange {
+                                wallet_address: wallet_address.clone(),
+                                amount_usdc: -Decimal::from(released) / Decimal::from(1_000_000),
+                            });
+                        }
+                        paid.push(ClaimLeaf {
+                            wallet_address,
+                            amount_wei: amount.to_string(),
+                        });
+                    }
+                    Payout::CarryForward { amount } if amount > 0 => {
+                        carried_forward_wei += amount;
+                        carry_forward.push(CarryForwardChange {
+                            wallet_address,
+                            amount_usdc: Decimal::from(amount) / Decimal::from(1_000_000),
+                        });
+                    }
+                    Payout::CarryForward { .. } => {}
+                }
+            }
+
+            if paid.is_empty() {
+                tracing::warn!("Every amount for {} is below its minimum payout", invention_id);
+                return Err(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+            }
+            tracing::info!(
+                "Minimum payout for {}: {} base units carried forward, {} released",
+                invention_id,
+                carried_forward_wei,
+                released_wei
+            );
+            paid
+        }
+        _ => merkle_leaves,
+    };
+
+    // Leaves sum exactly to the deposit
+    let total_deposit_wei = deposit_wei + released_wei - carried_forward_wei;
+    let deposit_usdc = Decimal::from(total_deposit_wei) / Decimal::from(1_000_000);
+
+    let revenue_breakdown = RevenueBreakdown {
+        fee_mode,
+        gross_usdc: revenue_usdc,
+        fees: fee_lines,
+        investor_pool_usdc: net_revenue,
+        carried_forward_usdc: Decimal::from(carried_forward_wei) / Decimal::from(1_000_000),
+        carry_forward_released_usdc: Decimal::from(released_wei) / Decimal::from(1_000_000),
+    };
+
+    // Amount each wallet gains from this distribution, keyed by canonical address
+    let added_wei: HashMap<String, String> = merkle_leaves
+        .iter()
+        .map(|leaf| (leaf.wallet_address.clone(), leaf.amount_wei.clone()))
+        .collect();
+
+    // Cumulative leaves commit to lifetime totals, so every wallet with history stays
+    // in the tree even when it gains nothing this round.
+    let merkle_leaves = match payload.mode {
+        DistributionMode::PerEpoch => merkle_leaves,
+        DistributionMode::Cumulative => {
+            let mut lifetime = fetch_cumulative_totals(pool, invention_id).await?;
+            lifetime.extend(merkle_leaves);
+            let (leaves, _) = canonicalize_claims(&lifetime).map_err(|e| {
+                tracing::error!("Failed to accumulate claims for invention {}: {}", invention_id, e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            leaves
+        }
+    };
+
+    // 3. Build Merkle tree (OpenZeppelin StandardMerkleTree layout), or check voucher limits
+    let tree = match payload.payout_method {
+        PayoutMethod::Merkle => {
+            let tree = StandardMerkleTree::of(payload.leaf_schema, &merkle_leaves).map_err(|e| {
+                tracing::error!("Failed to build merkle tree: {}", e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            Some(tree)
+        }
+        PayoutMethod::Voucher => {
+            if merkle_leaves.len() > MAX_VOUCHER_CLAIMS {
+                tracing::warn!(
+                    "Voucher distribution for {} has {} claims (max {})",
+                    invention_id,
+                    merkle_leaves.len(),
+                    MAX_VOUCHER_CLAIMS
+                );
+                return Err(axum::http::StatusCode::BAD_REQUEST);
+            }
+            if payload.voucher_deadline.is_some_and(|d| d <= chrono::Utc::now().timestamp()) {
+                return Err(axum::http::StatusCode::BAD_REQUEST);
+            }
+            None
+        }
+    };
+
+    Ok(DistributionPlan {
+        fee_count: fee_splits.len(),
+        net_revenue,
+        deposit_wei: total_deposit_wei,
+        deposit_usdc,
+        revenue_breakdown,
+        royalty_token,
+        dust,
+        dust_by_wallet,
+        roles,
+        merged_leaves,
+        added_wei,
+        leaves: merkle_leaves,
+        tree,
+        carry_forward,
+    })
+}
+
+/// Record that `address` is paid as `role`, keyed by its canonical (lowercase) form.
+fn add_role(roles: &mut HashMap<String, Vec<String>>, address: &str, role: &str) {
+    let entry = roles.entry(address.to_lowercase()).or_default();
+    if !entry.iter().any(|r| r == role) {
+        entry.push(role.to_string());
+    }
+}
+
+/// Fees to deduct before holders are paid, by the invention's fee mode.
+///
+/// ABS inventions (`fee_sharing_enabled`) use `compliance_fee_splits`; others use the
+/// ledger's platform fee and inventor share. Unknown inventions are not found, and
+/// inventions missing the configuration their mode needs are rejected.
+async fn resolve_fee_shares(
+    pool: &PgPool,
+    invention_id: &str,
+) -> Result<(FeeMode, Vec<FeeShare>), axum::http::StatusCode> {
+    let config = sqlx::query_as::<_, LedgerFeeConfig>(
         r#"
-        INSERT INTO dividend_distributions (id, invention_id, total_revenue_usdc, merkle_root, claim_count, created_at)
-        VALUES ($1, $2, $3, $4, $5, NOW())
+        SELECT fee_sharing_enabled, compliance_lawyer_id, platform_fee_percentage, platform_fee_address,
+               inventor_share_percentage, inventor_wallet_address
+        FROM invention_ledger WHERE invention_id = $1
         "#,
     )
-    .bind(distribution_id)
-    .bind(&invention_id)
-    .bind(revenue_usdc)
-    .bind(&merkle_root)
-    .bind(claims_data.len() as i32)
-    .execute(&pool)
+    .bind(invention_id)
+    .fetch_optional(pool)
     .await
     .map_err(|e| {
-        tracing::error!("Failed to insert distribution: {}", e);
+        tracing::error!("Failed to fetch fee configuration for {}: {}", invention_id, e);
         axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    let fee_mode = config.fee_mode();
+    let shares = match fee_mode {
+        FeeMode::Abs => {
+            let splits = sqlx::query_as::<_, FeeShare>(
+                "SELECT recipient_type, recipient_address, percentage FROM compliance_fee_splits WHERE invention_id = $1 ORDER BY created_at, id"
+            )
+            .bind(invention_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to fetch compliance fee splits: {}", e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            abs_fee_shares(&config, splits)
+        }
+        FeeMode::Standard => standard_fee_shares(&config),
+    }
+    .map_err(|e| {
+        tracing::warn!("Cannot distribute for {}: {}", invention_id, e);
+        axum::http::StatusCode::UNPROCESSABLE_ENTITY
     })?;
 
-    // 5. Store individual claims with Merkle proofs
-    for (i, (addr, amount_wei)) in claims_data.iter().enumerate() {
-        let proof = &proofs[i];
+    Ok((fee_mode, shares))
+}
 
-        // Convert wei-amount back to Decimal for DB storage (6 decimal places)
-        let amount_decimal = Decimal::from_str(amount_wei).unwrap_or_default() / Decimal::from(1_000_000);
-
-        sqlx::query(
-            r#"
-            INSERT INTO dividend_claims (id, distribution_id, wallet_address, amount_usdc, merkle_proof, claimed, created_at)
-            VALUES ($1, $2, $3, $4, $5, false, NOW())
-            "#,
-        )
-        .bind(Uuid::new_v4())
-        .bind(distribution_id)
-        .bind(addr)
-        .bind(amount_decimal)
-        .bind(proof)
-        .execute(&pool)
+/// Look up the invention's RoyaltyToken, if it has one.
+async fn fetch_royalty_token(
+    pool: &PgPool,
+    invention_id: &str,
+) -> Result<Option<ethers::types::Address>, axum::http::StatusCode> {
+    let address: Option<String> = sqlx::query_scalar(
+        "SELECT royalty_token_address FROM invention_ledger WHERE invention_id = $1",
+    )
+    .bind(invention_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch royalty token for {}: {}", invention_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .flatten();
+
+    address
+        .map(|a| {
+            a.parse().map_err(|_| {
+                tracing::error!("Invalid royalty_token_address for {}: {}", invention_id, a);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })
+        })
+        .transpose()
+}
+
+/// The invention's minimum payout in base units, if it has one.
+async fn fetch_min_payout(pool: &PgPool, invention_id: &str) -> Result<Option<u128>, axum::http::StatusCode> {
+    use rust_decimal::prelude::*;
+
+    let minimum: Option<Decimal> = sqlx::query_scalar(
+        "SELECT min_payout_usdc FROM invention_ledger WHERE invention_id = $1",
+    )
+    .bind(invention_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch minimum payout for {}: {}", invention_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .flatten();
+
+    minimum
+        .map(|m| {
+            (m * Decimal::from(1_000_000)).ceil().to_u128().ok_or_else(|| {
+                tracing::error!("Invalid min_payout_usdc for {}: {}", invention_id, m);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })
+        })
+        .transpose()
+}
+
+/// Carry-forward balance of every wallet of an invention that has one, in base units,
+/// by canonical address.
+async fn fetch_carry_forward_balances(
+    pool: &PgPool,
+    invention_id: &str,
+) -> Result<Vec<(String, u128)>, axum::http::StatusCode> {
+    use rust_decimal::prelude::*;
+
+    let balances: Vec<(String, Decimal)> = sqlx::query_as(
+        "SELECT wallet_address, balance_usdc FROM dividend_carry_forward_balances WHERE invention_id = $1",
+    )
+    .bind(invention_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load carry-forward balances for {}: {}", invention_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    balances
+        .into_iter()
+        .map(|(wallet_address, balance)| {
+            let balance_wei = (balance * Decimal::from(1_000_000)).round().to_u128().ok_or_else(|| {
+                tracing::error!("Invalid carry-forward balance for {} in {}: {}", wallet_address, invention_id, balance);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            Ok((wallet_address, balance_wei))
+        })
+        .collect()
+}
+
+/// Holder balances for a distribution.
+///
+/// Inventions with a RoyaltyToken are always snapshotted from chain at `snapshot_block`,
+/// so callers cannot choose their own shares. Balances are read from the Transfer index;
+/// the block must be final and already indexed. Returns `None` when the invention has
+/// no token and the caller-supplied balances should be used.
+async fn resolve_holders(
+    pool: &PgPool,
+    royalty_token: Option<ethers::types::Address>,
+    snapshot_block: Option<u64>,
+    supplied: &[HolderBalance],
+) -> Result<Option<Vec<HolderBalance>>, axum::http::StatusCode> {
+    let (token, block) = match (royalty_token, snapshot_block) {
+        (Some(token), Some(block)) if supplied.is_empty() => (token, block),
+        (None, None) => return Ok(None),
+        _ => return Err(axum::http::StatusCode::BAD_REQUEST),
+    };
+
+    // Balances are only indexed once final; a newer block could still be reorged away
+    let rpc_url = std::env::var("RPC_URL")
+        .unwrap_or_else(|_| "http://127.0.0.1:8545".to_string());
+    let head = fetch_block_number(&rpc_url).await.map_err(|e| {
+        tracing::error!("Failed to fetch block number: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if head.checked_sub(TOKEN_INDEX_CONFIRMATIONS).is_none_or(|safe_head| block > safe_head) {
+        tracing::warn!("Snapshot block {} is not final at head {}", block, head);
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    let holders = snapshot_holders_from_index(pool, token, block)
         .await
         .map_err(|e| {
-            tracing::error!("Failed to insert claim for {}: {}", addr, e);
+            tracing::error!("Failed to read indexed balances of {:?} at block {}: {}", token, block, e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or_else(|| {
+            tracing::warn!("Transfer index of {:?} has not reached block {} yet", token, block);
+            axum::http::StatusCode::CONFLICT
         })?;
-    }
 
-    tracing::info!(
-        "Distribution {} created: root={}, claims={}",
-        distribution_id,
-        merkle_root,
-        claims_data.len()
-    );
+    Ok(Some(into_holder_balances(holders)))
+}
 
-    let distribution = DividendDistribution {
-        id: distribution_id,
-        invention_id,
-        total_revenue_usdc: revenue_usdc,
-        merkle_root,
-        claim_count: claims_data.len() as i32,
-        created_at: chrono::Utc::now(),
+/// Current chain head.
+async fn fetch_block_number(rpc_url: &str) -> anyhow::Result<u64> {
+    use ethers::providers::{Http, Middleware, Provider};
+
+    let provider = Provider::<Http>::try_from(rpc_url)?;
+    Ok(provider.get_block_number().await?.as_u64())
+}
+
+fn into_holder_balances(holders: Vec<(String, rust_decimal::Decimal)>) -> Vec<HolderBalance> {
+    holders
+        .into_iter()
+        .map(|(wallet_address, token_balance)| HolderBalance {
+            wallet_address,
+            token_balance,
+        })
+        .collect()
+}
+
+/// Sign one voucher per claim `(id, wallet_address, amount_usdc)` with the Vault signing key,
+/// redeemable from DividendVault voucher epoch `epoch`.
+/// Each voucher's nonce is derived from the ID of the claim row it belongs to.
+fn sign_vouchers(
+    distribution_id: Uuid,
+    claims: &[(Uuid, String, rust_decimal::Decimal)],
+    deadline: u64,
+    epoch: u64,
+) -> Result<Vec<(Uuid, SignedVoucher)>, axum::http::StatusCode> {
+    use rust_decimal::prelude::*;
+
+    let signer = signer_from_env().map_err(|e| {
+        tracing::error!("Voucher signer misconfigured: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let Some((wallet, chain_id)) = signer else {
+        tracing::error!("VAULT_ATTESTATION_PRIVATE_KEY not set - cannot issue vouchers");
+        return Err(axum::http::StatusCode::SERVICE_UNAVAILABLE);
     };
 
-    Ok(Json(distribution))
+    claims
+        .iter()
+        .map(|(claim_id, wallet_address, amount_usdc)| {
+            let amount = (amount_usdc * Decimal::from(1_000_000))
+                .round()
+                .to_u128()
+                .ok_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+            let voucher = ClaimVoucher {
+                recipient: wallet_address.clone(),
+                amount: amount.to_string(),
+                nonce: claim_id.as_u128().to_string(),
+                deadline,
+                epoch,
+                distribution_id: distribution_id.to_string(),
+                chain_id,
+            };
+
+            let signed = voucher.sign(&wallet).map_err(|e| {
+                tracing::error!("Failed to sign voucher for {}: {}", wallet_address, e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            Ok((*claim_id, signed))
+        })
+        .collect()
+}
+
+/// Sign an EIP-712 attestation for a new distribution with the configured key, if any.
+fn attest_distribution(
+    invention_id: &str,
+    distribution_id: Uuid,
+    merkle_root: &str,
+    total_usdc: rust_decimal::Decimal,
+    claim_count: usize,
+) -> Result<Option<SignedAttestation>, axum::http::StatusCode> {
+    let signer = signer_from_env().map_err(|e| {
+        tracing::error!("Attestation signer misconfigured: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let Some((wallet, chain_id)) = signer else {
+        tracing::warn!("VAULT_ATTESTATION_PRIVATE_KEY not set - distribution {} is not attested", distribution_id);
+        return Ok(None);
+    };
+
+    let attestation = DistributionAttestation {
+        invention_id: invention_id.to_string(),
+        distribution_id: distribution_id.to_string(),
+        merkle_root: merkle_root.to_string(),
+        total_amount: (total_usdc * rust_decimal::Decimal::from(1_000_000)).round().to_string(),
+        claim_count: claim_count as u64,
+        chain_id,
+    };
+
+    attestation.sign(&wallet).map(Some).map_err(|e| {
+        tracing::error!("Failed to sign attestation for {}: {}", distribution_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// Lifetime totals of each wallet across an invention's published or closed cumulative
+/// distributions, in base units. Drafts and approved distributions may still be vetoed
+/// or cancelled, so their amounts are not yet owed.
+async fn fetch_cumulative_totals(
+    pool: &PgPool,
+    invention_id: &str,
+) -> Result<Vec<ClaimLeaf>, axum::http::StatusCode> {
+    use rust_decimal::Decimal;
+
+    let totals: Vec<(String, Decimal)> = sqlx::query_as(
+        r#"
+        SELECT LOWER(c.wallet_address), SUM(c.amount_usdc)
+        FROM dividend_claims c
+        JOIN dividend_distributions d ON d.id = c.distribution_id
+        WHERE d.invention_id = $1 AND d.mode = 'CUMULATIVE' AND d.status IN ('PUBLISHED', 'CLOSED')
+        GROUP BY LOWER(c.wallet_address)
+        "#,
+    )
+    .bind(invention_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load cumulative totals for {}: {}", invention_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(totals
+        .into_iter()
+        .map(|(wallet_address, total)| ClaimLeaf {
+            wallet_address,
+            amount_wei: (total * Decimal::from(1_000_000)).round().to_string(),
+        })
+        .collect())
 }
 
 /// GET /api/v1/vault/dividends/claims/:wallet_address
 /// Get all claimable dividends for a wallet address.
+///
+/// Only published per-epoch distributions are included. Cumulative roots are never
+/// posted to a distributor, so their proofs could not be claimed against.
 async fn get_claimable(
     State(pool): State<PgPool>,
     Path(wallet_address): Path<String>,
 ) -> Result<Json<Vec<DividendClaim>>, axum::http::StatusCode> {
-    let claims = sqlx::query_as::<_, DividendClaim>(
+    let mut claims = sqlx::query_as::<_, DividendClaim>(
         r#"
-        SELECT * FROM dividend_claims
-        WHERE wallet_address = $1 AND claimed = false
-        ORDER BY created_at DESC
+        SELECT c.*, d.epoch
+        FROM dividend_claims c
+        JOIN dividend_distributions d ON d.id = c.distribution_id
+        WHERE LOWER(c.wallet_address) = LOWER($1) AND c.claimed = false AND d.status = 'PUBLISHED'
+          AND d.mode = 'PER_EPOCH'
+          AND (c.voucher IS NULL OR (c.voucher->'message'->>'deadline')::BIGINT > EXTRACT(EPOCH FROM NOW()))
+        ORDER BY c.created_at DESC
         "#,
     )
     .bind(&wallet_address)
@@ -240,5 +966,1654 @@ async fn get_claimable(
     .await
     .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    // Claims backed by a stored tree have their proof derived on request
+    attach_stored_proofs(&pool, &mut claims).await?;
+
     Ok(Json(claims))
 }
+
+/// A claim and the distribution facts its `claimDividend` call depends on.
+#[derive(sqlx::FromRow)]
+struct ClaimCalldataRow {
+    wallet_address: String,
+    amount_usdc: rust_decimal::Decimal,
+    tree_index: Option<i32>,
+    claimed: bool,
+    expired: bool,
+    distribution_id: Uuid,
+    status: String,
+    mode: String,
+    payout_method: String,
+    epoch: Option<i64>,
+    dividend_vault_address: Option<String>,
+    merkle_root: Option<String>,
+    leaf_schema: sqlx::types::Json<StandardLeafSchema>,
+}
+
+/// GET /api/v1/vault/dividends/claims/:wallet_address/:claim_id/calldata
+/// Ready-to-send `claimDividend(epoch, amount, merkleProof)` calldata for an open claim
+/// of a published per-epoch Merkle distribution. The proof is checked against the
+/// stored root first, so the call will not revert with "Invalid proof".
+async fn get_claim_calldata(
+    State(pool): State<PgPool>,
+    Path((wallet_address, claim_id)): Path<(String, Uuid)>,
+) -> Result<Json<ClaimCalldata>, axum::http::StatusCode> {
+    use ethers::types::{H256, U256};
+    use rust_decimal::prelude::*;
+
+    let row = sqlx::query_as::<_, ClaimCalldataRow>(
+        r#"
+        SELECT c.wallet_address, c.amount_usdc, c.tree_index, c.claimed, c.expired_at IS NOT NULL AS expired,
+               d.id AS distribution_id, d.status, d.mode, d.payout_method, d.epoch, d.dividend_vault_address,
+               d.merkle_root, d.leaf_schema
+        FROM dividend_claims c
+        JOIN dividend_distributions d ON d.id = c.distribution_id
+        WHERE c.id = $1 AND LOWER(c.wallet_address) = LOWER($2)
+        "#,
+    )
+    .bind(claim_id)
+    .bind(&wallet_address)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load claim {}: {}", claim_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    // Only open claims of roots DividendVault holds can be claimed with this call
+    let claimable_kind = row.payout_method == PayoutMethod::Merkle.as_str()
+        && row.mode == DistributionMode::PerEpoch.as_str()
+        && row.leaf_schema.0 == StandardLeafSchema::default();
+    let (Some(epoch), Some(vault), Some(merkle_root), Some(tree_index)) =
+        (row.epoch, &row.dividend_vault_address, &row.merkle_root, row.tree_index)
+    else {
+        tracing::warn!("Claim {} is not part of a distribution published to DividendVault", claim_id);
+        return Err(axum::http::StatusCode::CONFLICT);
+    };
+    if !claimable_kind || row.status != DistributionStatus::Published.as_str() || row.claimed || row.expired {
+        tracing::warn!(
+            "Claim {} cannot be claimed on-chain (distribution {} is {}, claimed={}, expired={})",
+            claim_id,
+            row.distribution_id,
+            row.status,
+            row.claimed,
+            row.expired
+        );
+        return Err(axum::http::StatusCode::CONFLICT);
+    }
+
+    let chain_id: u64 = std::env::var("CHAIN_ID")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| {
+            tracing::warn!("Claim calldata requested but CHAIN_ID is not configured");
+            axum::http::StatusCode::SERVICE_UNAVAILABLE
+        })?;
+
+    // Claims are stored in USDC; the contract takes base units
+    let scaled = row.amount_usdc * Decimal::from(1_000_000);
+    let amount_wei = Some(scaled)
+        .filter(|s| s.fract().is_zero())
+        .and_then(|s| s.to_u128())
+        .ok_or_else(|| {
+            tracing::error!("Claim {} amount {} is not whole base units", claim_id, row.amount_usdc);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let merkle_proof = fetch_stored_proof(&pool, row.distribution_id, tree_index).await?;
+    let leaf = ClaimLeaf {
+        wallet_address: row.wallet_address.clone(),
+        amount_wei: amount_wei.to_string(),
+    };
+    if !verify_proof(&row.leaf_schema.0, merkle_root, &leaf, &merkle_proof).unwrap_or(false) {
+        tracing::error!("Stored proof for claim {} does not verify against root {}", claim_id, merkle_root);
+        return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let proof_nodes = merkle_proof
+        .iter()
+        .map(|node| node.parse::<H256>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let calldata =
+        distribution_publisher::claim_dividend_calldata(epoch as u64, U256::from(amount_wei), &proof_nodes);
+
+    Ok(Json(ClaimCalldata {
+        claim_id,
+        wallet_address: row.wallet_address,
+        dividend_vault_address: vault.clone(),
+        chain_id,
+        epoch,
+        amount_wei: amount_wei.to_string(),
+        amount_usdc: row.amount_usdc,
+        merkle_proof,
+        calldata: format!("0x{}", hex::encode(&calldata)),
+    }))
+}
+
+/// Look up a distribution's root and the leaf schema its tree was built with.
+/// Voucher distributions have no tree and are reported as not found.
+async fn fetch_root_and_schema(
+    pool: &PgPool,
+    distribution_id: Uuid,
+) -> Result<(String, StandardLeafSchema), axum::http::StatusCode> {
+    let (merkle_root, leaf_schema): (String, sqlx::types::Json<StandardLeafSchema>) = sqlx::query_as(
+        "SELECT merkle_root, leaf_schema FROM dividend_distributions WHERE id = $1 AND payout_method = 'MERKLE'",
+    )
+    .bind(distribution_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load distribution {}: {}", distribution_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    Ok((merkle_root, leaf_schema.0))
+}
+
+/// Read a claim's proof straight from the stored tree, fetching only its `O(log n)` sibling nodes.
+async fn fetch_stored_proof(
+    pool: &PgPool,
+    distribution_id: Uuid,
+    tree_index: i32,
+) -> Result<Vec<String>, axum::http::StatusCode> {
+    let path: Vec<i32> = proof_path(tree_index as usize)
+        .into_iter()
+        .map(|i| i as i32)
+        .collect();
+
+    let nodes: Vec<Vec<u8>> = sqlx::query_scalar(
+        r#"
+        SELECT substring(t.nodes FROM p.node * 32 + 1 FOR 32)
+        FROM dividend_merkle_trees t, UNNEST($2::INT[]) WITH ORDINALITY AS p(node, ord)
+        WHERE t.distribution_id = $1
+        ORDER BY p.ord
+        "#,
+    )
+    .bind(distribution_id)
+    .bind(&path)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to read proof nodes for {}: {}", distribution_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if nodes.len() != path.len() {
+        tracing::error!("Stored tree for distribution {} is missing", distribution_id);
+        return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    Ok(nodes.iter().map(|n| format!("0x{}", hex::encode(n))).collect())
+}
+
+/// Fill in `merkle_proof` for every claim with a `tree_index`, reading all of their
+/// sibling nodes from the stored trees in one query.
+async fn attach_stored_proofs(pool: &PgPool, claims: &mut [DividendClaim]) -> Result<(), axum::http::StatusCode> {
+    // One row per proof node: (claim position, distribution, node index, order in proof)
+    let mut positions = Vec::new();
+    let mut distribution_ids = Vec::new();
+    let mut nodes = Vec::new();
+    let mut orders = Vec::new();
+    for (position, claim) in claims.iter().enumerate() {
+        let Some(tree_index) = claim.tree_index else { continue };
+        for (order, node) in proof_path(tree_index as usize).into_iter().enumerate() {
+            positions.push(position as i32);
+            distribution_ids.push(claim.distribution_id);
+            nodes.push(node as i32);
+            orders.push(order as i32);
+        }
+    }
+    if positions.is_empty() {
+        return Ok(());
+    }
+
+    let rows: Vec<(i32, Vec<u8>)> = sqlx::query_as(
+        r#"
+        SELECT p.position, substring(t.nodes FROM p.node * 32 + 1 FOR 32)
+        FROM UNNEST($1::INT[], $2::UUID[], $3::INT[], $4::INT[]) AS p(position, distribution_id, node, ord)
+        JOIN dividend_merkle_trees t ON t.distribution_id = p.distribution_id
+        ORDER BY p.position, p.ord
+        "#,
+    )
+    .bind(&positions)
+    .bind(&distribution_ids)
+    .bind(&nodes)
+    .bind(&orders)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to read proof nodes: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if rows.len() != positions.len() {
+        tracing::error!("Stored trees are missing for {} proof nodes", positions.len() - rows.len());
+        return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    for claim in claims.iter_mut().filter(|c| c.tree_index.is_some()) {
+        claim.merkle_proof.clear();
+    }
+    for (position, node) in rows {
+        claims[position as usize].merkle_proof.push(format!("0x{}", hex::encode(node)));
+    }
+    Ok(())
+}
+
+/// GET /api/v1/vault/dividends/distributions/:id/tree
+/// Download the stored tree: `2n - 1` 32-byte nodes in StandardMerkleTree array order.
+/// The leaf count is returned in the `X-Merkle-Leaf-Count` header.
+async fn download_tree(
+    State(pool): State<PgPool>,
+    Path(distribution_id): Path<Uuid>,
+) -> Result<impl IntoResponse, axum::http::StatusCode> {
+    let stored: Option<(i32, Vec<u8>)> = sqlx::query_as(
+        "SELECT leaf_count, nodes FROM dividend_merkle_trees WHERE distribution_id = $1",
+    )
+    .bind(distribution_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let (leaf_count, nodes) = match stored {
+        Some(stored) => stored,
+        None => backfill_tree(&pool, distribution_id).await?,
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (HeaderName::from_static("x-merkle-leaf-count"), leaf_count.to_string()),
+        ],
+        nodes,
+    ))
+}
+
+/// Build and store the tree for a distribution created before trees were persisted.
+///
+/// Claims are streamed through a Postgres cursor into `MerkleTreeBuilder`, so memory
+/// stays bounded by the leaf hashes rather than the claim rows. Hashing runs on a
+/// blocking thread fed through a bounded channel. The result is kept only if it
+/// reproduces the distribution's root; each claim gets its leaf position.
+async fn backfill_tree(
+    pool: &PgPool,
+    distribution_id: Uuid,
+) -> Result<(i32, Vec<u8>), axum::http::StatusCode> {
+    use futures::StreamExt;
+    use rust_decimal::Decimal;
+
+    let (merkle_root, leaf_schema) = fetch_root_and_schema(pool, distribution_id).await?;
+
+    let (leaves, mut pending) = tokio::sync::mpsc::channel::<ClaimLeaf>(BACKFILL_CHANNEL_CAPACITY);
+    let building = tokio::task::spawn_blocking(move || {
+        let mut builder = MerkleTreeBuilder::with_schema(leaf_schema, 0);
+        while let Some(leaf) = pending.blocking_recv() {
+            builder.push(leaf)?;
+        }
+        anyhow::ensure!(builder.len() > 0, "distribution has no claims");
+        builder.finish()
+    });
+
+    let mut claim_ids: Vec<Uuid> = Vec::new();
+    let mut rows = sqlx::query_as::<_, (Uuid, String, Decimal)>(
+        "SELECT id, wallet_address, COALESCE(cumulative_amount_usdc, amount_usdc) FROM dividend_claims WHERE distribution_id = $1",
+    )
+    .bind(distribution_id)
+    .fetch(pool);
+
+    while let Some(row) = rows.next().await {
+        let (id, wallet_address, amount_usdc) =
+            row.map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        claim_ids.push(id);
+        let leaf = ClaimLeaf {
+            wallet_address,
+            amount_wei: (amount_usdc * Decimal::from(1_000_000)).round().to_string(),
+        };
+        // The builder stopped on an invalid claim; its error is reported below
+        if leaves.send(leaf).await.is_err() {
+            break;
+        }
+    }
+    drop(rows);
+    drop(leaves);
+
+    if claim_ids.is_empty() {
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
+
+    let tree = building
+        .await
+        .map_err(|e| {
+            tracing::error!("Tree rebuild for distribution {} panicked: {}", distribution_id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .map_err(|e| {
+            tracing::error!("Failed to rebuild tree for distribution {}: {}", distribution_id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    // Distributions built with the legacy padded layout cannot be backfilled
+    let root = format!("0x{}", hex::encode(tree.root()));
+    if !root.eq_ignore_ascii_case(&merkle_root) {
+        tracing::warn!(
+            "Distribution {} root {} does not match rebuilt root {}",
+            distribution_id,
+            merkle_root,
+            root
+        );
+        return Err(axum::http::StatusCode::CONFLICT);
+    }
+
+    let leaf_count = tree.len() as i32;
+    let (nodes, leaf_indices) = tree.into_parts();
+    let nodes = nodes.concat();
+    let tree_indices: Vec<i32> = leaf_indices.into_iter().map(|i| i as i32).collect();
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // A concurrent backfill may have stored the same tree first; the indices written
+    // below are the same either way
+    sqlx::query(
+        "INSERT INTO dividend_merkle_trees (distribution_id, leaf_count, nodes, created_at) VALUES ($1, $2, $3, NOW())
+         ON CONFLICT (distribution_id) DO NOTHING",
+    )
+    .bind(distribution_id)
+    .bind(leaf_count)
+    .bind(&nodes)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to store backfilled tree for {}: {}", distribution_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    sqlx::query(
+        r#"
+        UPDATE dividend_claims c SET tree_index = u.tree_index
+        FROM UNNEST($1::UUID[], $2::INT[]) AS u(id, tree_index)
+        WHERE c.id = u.id
+        "#,
+    )
+    .bind(&claim_ids)
+    .bind(&tree_indices)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to backfill tree indices for {}: {}", distribution_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tx.commit()
+        .await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tracing::info!("Backfilled tree for distribution {} ({} leaves)", distribution_id, leaf_count);
+
+    Ok((leaf_count, nodes))
+}
+
+/// Request body for checking a claim against a stored distribution.
+#[derive(serde::Deserialize)]
+struct VerifyClaimRequest {
+    distribution_id: Uuid,
+    wallet_address: String,
+    /// Amount in USDC base units (6 decimals), exactly as passed to `claimDividend`.
+    amount_wei: String,
+    merkle_proof: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+struct VerifyClaimResponse {
+    distribution_id: Uuid,
+    merkle_root: String,
+    valid: bool,
+}
+
+/// POST /api/v1/vault/dividends/verify-claim
+/// Check a (wallet, amount, proof) triple against a distribution's Merkle root.
+/// Lets wallets tell a holder whether `claimDividend` would revert with "Invalid proof"
+/// before any gas is spent.
+async fn verify_claim(
+    State(pool): State<PgPool>,
+    Json(req): Json<VerifyClaimRequest>,
+) -> Result<Json<VerifyClaimResponse>, axum::http::StatusCode> {
+    let (merkle_root, leaf_schema) = fetch_root_and_schema(&pool, req.distribution_id).await?;
+
+    let leaf = ClaimLeaf {
+        wallet_address: req.wallet_address,
+        amount_wei: req.amount_wei,
+    };
+
+    let valid = verify_proof(&leaf_schema, &merkle_root, &leaf, &req.merkle_proof).map_err(|e| {
+        tracing::warn!("Malformed claim verification request: {}", e);
+        axum::http::StatusCode::BAD_REQUEST
+    })?;
+
+    Ok(Json(VerifyClaimResponse {
+        distribution_id: req.distribution_id,
+        merkle_root,
+        valid,
+    }))
+}
+
+/// GET /api/v1/vault/dividends/distributions/:id/standard-tree
+/// Export a distribution as an OpenZeppelin `StandardMerkleTree` JSON dump,
+/// loadable with `StandardMerkleTree.load()` from `@openzeppelin/merkle-tree`.
+async fn export_standard_tree(
+    State(pool): State<PgPool>,
+    Path(distribution_id): Path<Uuid>,
+) -> Result<Json<StandardTreeDump>, axum::http::StatusCode> {
+    let (tree, _) = load_distribution_tree(&pool, distribution_id).await?;
+    Ok(Json(tree.dump()))
+}
+
+/// Load a distribution's tree: from the stored blob when present, otherwise
+/// rebuilt from its claims. Returns the tree and claim IDs in the tree's insertion order.
+async fn load_distribution_tree(
+    pool: &PgPool,
+    distribution_id: Uuid,
+) -> Result<(StandardMerkleTree, Vec<Uuid>), axum::http::StatusCode> {
+    use rust_decimal::Decimal;
+
+    let (merkle_root, leaf_schema) = fetch_root_and_schema(pool, distribution_id).await?;
+
+    let rows: Vec<(Uuid, String, Decimal, Option<i32>)> = sqlx::query_as(
+        "SELECT id, wallet_address, COALESCE(cumulative_amount_usdc, amount_usdc), tree_index FROM dividend_claims WHERE distribution_id = $1 ORDER BY wallet_address",
+    )
+    .bind(distribution_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let stored_nodes: Option<Vec<u8>> = sqlx::query_scalar(
+        "SELECT nodes FROM dividend_merkle_trees WHERE distribution_id = $1",
+    )
+    .bind(distribution_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let claim_ids = rows.iter().map(|(id, _, _, _)| *id).collect();
+    let values: Vec<(ClaimLeaf, Option<i32>)> = rows
+        .into_iter()
+        .map(|(_, wallet_address, amount_usdc, tree_index)| {
+            let leaf = ClaimLeaf {
+                wallet_address,
+                amount_wei: (amount_usdc * Decimal::from(1_000_000)).round().to_string(),
+            };
+            (leaf, tree_index)
+        })
+        .collect();
+
+    let indexed: Option<Vec<(ClaimLeaf, usize)>> = values
+        .iter()
+        .map(|(leaf, tree_index)| tree_index.map(|i| (leaf.clone(), i as usize)))
+        .collect();
+
+    let tree = match (stored_nodes, indexed) {
+        (Some(nodes), Some(indexed)) => StandardMerkleTree::from_bytes(leaf_schema, &nodes, indexed),
+        _ => {
+            let leaves: Vec<ClaimLeaf> = values.into_iter().map(|(leaf, _)| leaf).collect();
+            StandardMerkleTree::of(leaf_schema, &leaves)
+        }
+    }
+    .map_err(|e| {
+        tracing::error!("Failed to load tree for distribution {}: {}", distribution_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    // Distributions built with the legacy padded layout cannot be expressed in this format
+    if !tree.root().eq_ignore_ascii_case(&merkle_root) {
+        tracing::warn!(
+            "Distribution {} root {} does not match StandardMerkleTree root {}",
+            distribution_id,
+            merkle_root,
+            tree.root()
+        );
+        return Err(axum::http::StatusCode::CONFLICT);
+    }
+
+    Ok((tree, claim_ids))
+}
+
+/// Request body for importing a tree built with `@openzeppelin/merkle-tree`.
+#[derive(serde::Deserialize)]
+struct ImportTreeRequest {
+    /// Root the caller expects, e.g. printed by the JS script that built the tree.
+    merkle_root: String,
+    tree: StandardTreeDump,
+}
+
+/// POST /api/v1/vault/dividends/import/:invention_id
+/// Import a `StandardMerkleTree` dump as a distribution with one claim per leaf.
+/// The dump is fully re-hashed and must reproduce `merkle_root` before anything is stored.
+/// The distribution, its tree, claims and audit entry are stored in one transaction.
+async fn import_standard_tree(
+    State(pool): State<PgPool>,
+    Path(invention_id): Path<String>,
+    AuthenticatedActor(actor): AuthenticatedActor,
+    Json(req): Json<ImportTreeRequest>,
+) -> Result<Json<DividendDistribution>, axum::http::StatusCode> {
+    use rust_decimal::prelude::*;
+
+    let known: Option<i32> = sqlx::query_scalar("SELECT 1 FROM invention_ledger WHERE invention_id = $1")
+        .bind(&invention_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to look up invention {}: {}", invention_id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if known.is_none() {
+        tracing::warn!("Rejected tree import for unknown invention {}", invention_id);
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
+
+    let tree = StandardMerkleTree::load(&req.tree).map_err(|e| {
+        tracing::warn!("Rejected tree import for invention {}: {}", invention_id, e);
+        axum::http::StatusCode::BAD_REQUEST
+    })?;
+
+    // DividendVault allows one claim per address per epoch, so duplicate leaves are unclaimable
+    let leaves: Vec<ClaimLeaf> = tree.claims().cloned().collect();
+    let (_, merged) = canonicalize_claims(&leaves).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    if !merged.is_empty() {
+        tracing::warn!("Rejected tree import with {} duplicate addresses", merged.len());
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    let merkle_root = tree.root();
+    if !merkle_root.eq_ignore_ascii_case(&req.merkle_root) {
+        tracing::warn!(
+            "Imported tree root {} does not match expected root {}",
+            merkle_root,
+            req.merkle_root
+        );
+        return Err(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    // Convert base units back to USDC (6 decimals)
+    let mut claims = Vec::with_capacity(tree.len());
+    for (i, claim) in tree.claims().enumerate() {
+        let amount_usdc = Decimal::from_str(&claim.amount_wei)
+            .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?
+            / Decimal::from(1_000_000);
+        let tree_index = tree.tree_index(i).ok_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        claims.push(NewClaim {
+            id: Uuid::new_v4(),
+            wallet_address: claim.wallet_address.clone(),
+            amount_usdc,
+            cumulative_amount_usdc: None,
+            tree_index: Some(tree_index as i32),
+        });
+    }
+    let total_usdc: Decimal = claims.iter().map(|c| c.amount_usdc).sum();
+
+    let audit_payload = serde_json::json!({
+        "source": "openzeppelin-standard-v1",
+        "merkle_root": merkle_root,
+        "leaf_schema": tree.schema(),
+        "total_usdc": total_usdc.to_string(),
+        "claim_count": claims.len(),
+    });
+
+    let distribution = DividendDistribution {
+        id: Uuid::new_v4(),
+        invention_id,
+        total_revenue_usdc: total_usdc,
+        merkle_root: Some(merkle_root),
+        claim_count: claims.len() as i32,
+        created_at: chrono::Utc::now(),
+        status: DistributionStatus::Draft,
+        epoch: None,
+        publish_tx_hash: None,
+        payout_method: PayoutMethod::Merkle,
+        voucher_deadline: None,
+        mode: DistributionMode::PerEpoch,
+        snapshot_block: None,
+        leaf_schema: tree.schema(),
+        merged_leaves: Vec::new(),
+        revenue_breakdown: None,
+        dust: None,
+        attestation: None,
+    };
+
+    let record = DistributionRecord {
+        distribution: &distribution,
+        tree: Some(&tree),
+        claims,
+        carry_forward: Vec::new(),
+        audit_event: "DIVIDEND_DISTRIBUTION_IMPORT",
+        actor: &actor,
+        audit_payload,
+        idempotency_key: None,
+    };
+    distribution_store::store_distribution(&pool, &record).await.map_err(|e| {
+        tracing::error!("Failed to store imported distribution for {}: {}", distribution.invention_id, e);
+        axum::http::StatusCode::from(e)
+    })?;
+
+    tracing::info!(
+        "Imported distribution {} for invention {} by {}: root={}, claims={}",
+        distribution.id,
+        distribution.invention_id,
+        actor,
+        distribution.merkle_root.as_deref().unwrap_or("-"),
+        distribution.claim_count
+    );
+
+    Ok(Json(distribution))
+}
+
+/// Request body for batch-claim multiproofs.
+#[derive(serde::Deserialize)]
+struct MultiProofRequest {
+    claim_ids: Vec<Uuid>,
+}
+
+#[derive(serde::Serialize)]
+struct MultiProofLeaf {
+    claim_id: Uuid,
+    wallet_address: String,
+    amount_wei: String,
+}
+
+/// One multiproof per distribution (one tree per epoch).
+#[derive(serde::Serialize)]
+struct DistributionMultiProof {
+    distribution_id: Uuid,
+    merkle_root: String,
+    /// Leaves in the order `MerkleProof.multiProofVerify` expects them.
+    leaves: Vec<MultiProofLeaf>,
+    proof: Vec<String>,
+    proof_flags: Vec<bool>,
+}
+
+/// A requested claim with what its multiproof needs from the claim and distribution rows.
+#[derive(sqlx::FromRow)]
+struct MultiProofClaimRow {
+    id: Uuid,
+    distribution_id: Uuid,
+    wallet_address: String,
+    amount_usdc: rust_decimal::Decimal,
+    tree_index: Option<i32>,
+    status: String,
+    payout_method: String,
+    merkle_root: Option<String>,
+    leaf_schema: sqlx::types::Json<StandardLeafSchema>,
+    leaf_count: Option<i32>,
+}
+
+/// POST /api/v1/vault/dividends/multiproof
+/// Build OpenZeppelin-compatible multiproofs for a set of claims, grouped per distribution.
+/// Lets recipients holding several leaves (treasuries, lawyer wallets) claim them in one call.
+///
+/// Only the proof nodes are read from each stored tree, so the cost grows with the
+/// number of claims asked for rather than the size of the distribution.
+async fn get_multiproofs(
+    State(pool): State<PgPool>,
+    Json(mut req): Json<MultiProofRequest>,
+) -> Result<Json<Vec<DistributionMultiProof>>, axum::http::StatusCode> {
+    if req.claim_ids.is_empty() {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    // A leaf is proven once however often it is asked for
+    req.claim_ids.sort_unstable();
+    req.claim_ids.dedup();
+
+    let mut rows = fetch_multiproof_claims(&pool, &req.claim_ids).await?;
+    if rows.len() != req.claim_ids.len() {
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
+    // Voucher distributions have no tree
+    if rows.iter().any(|r| r.payout_method != PayoutMethod::Merkle.as_str()) {
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
+    if let Some(row) = rows.iter().find(|r| r.status != DistributionStatus::Published.as_str()) {
+        tracing::warn!("Refused multiproof for distribution {} in status {}", row.distribution_id, row.status);
+        return Err(axum::http::StatusCode::CONFLICT);
+    }
+
+    // Distributions created before trees were persisted get theirs stored once
+    let unstored: std::collections::BTreeSet<Uuid> =
+        rows.iter().filter(|r| r.leaf_count.is_none()).map(|r| r.distribution_id).collect();
+    if !unstored.is_empty() {
+        for distribution_id in unstored {
+            backfill_tree(&pool, distribution_id).await?;
+        }
+        rows = fetch_multiproof_claims(&pool, &req.claim_ids).await?;
+    }
+
+    let mut by_distribution: std::collections::BTreeMap<Uuid, Vec<MultiProofClaimRow>> = Default::default();
+    for row in rows {
+        by_distribution.entry(row.distribution_id).or_default().push(row);
+    }
+
+    let mut response = Vec::with_capacity(by_distribution.len());
+    for (distribution_id, claims) in by_distribution {
+        response.push(build_multiproof(&pool, distribution_id, claims).await?);
+    }
+
+    Ok(Json(response))
+}
+
+/// Load the claims `claim_ids` with their distribution and stored tree size.
+async fn fetch_multiproof_claims(
+    pool: &PgPool,
+    claim_ids: &[Uuid],
+) -> Result<Vec<MultiProofClaimRow>, axum::http::StatusCode> {
+    sqlx::query_as::<_, MultiProofClaimRow>(
+        r#"
+        SELECT c.id, c.distribution_id, c.wallet_address, COALESCE(c.cumulative_amount_usdc, c.amount_usdc) AS amount_usdc,
+               c.tree_index, d.status, d.payout_method, d.merkle_root, d.leaf_schema, t.leaf_count
+        FROM dividend_claims c
+        JOIN dividend_distributions d ON d.id = c.distribution_id
+        LEFT JOIN dividend_merkle_trees t ON t.distribution_id = c.distribution_id
+        WHERE c.id = ANY($1)
+        "#,
+    )
+    .bind(claim_ids)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load claims for multiproof: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// Build and check the multiproof for `claims` of `distribution_id`, reading only
+/// the proof nodes from its stored tree.
+async fn build_multiproof(
+    pool: &PgPool,
+    distribution_id: Uuid,
+    claims: Vec<MultiProofClaimRow>,
+) -> Result<DistributionMultiProof, axum::http::StatusCode> {
+    use std::collections::HashMap;
+
+    let internal = |message: String| {
+        tracing::error!("Multiproof for distribution {}: {}", distribution_id, message);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+
+    let first = &claims[0];
+    let merkle_root = first.merkle_root.clone().ok_or_else(|| internal("no Merkle root".to_string()))?;
+    let schema = first.leaf_schema.0;
+    let leaf_count = first.leaf_count.ok_or_else(|| internal("no stored tree".to_string()))? as usize;
+
+    let mut by_tree_index: HashMap<usize, &MultiProofClaimRow> = HashMap::with_capacity(claims.len());
+    for claim in &claims {
+        let tree_index = claim
+            .tree_index
+            .ok_or_else(|| internal(format!("claim {} has no leaf position", claim.id)))?;
+        if by_tree_index.insert(tree_index as usize, claim).is_some() {
+            return Err(internal(format!("leaf position {} is shared by several claims", tree_index)));
+        }
+    }
+    let tree_indices: Vec<usize> = by_tree_index.keys().copied().collect();
+
+    let layout = multiproof_layout(2 * leaf_count - 1, &tree_indices).map_err(|e| internal(e.to_string()))?;
+
+    let nodes: Vec<i32> = layout.proof.iter().map(|&i| i as i32).collect();
+    let orders: Vec<i32> = (0..nodes.len() as i32).collect();
+    let proof_nodes: Vec<Vec<u8>> = sqlx::query_scalar(
+        r#"
+        SELECT substring(t.nodes FROM p.node * 32 + 1 FOR 32)
+        FROM UNNEST($2::INT[], $3::INT[]) AS p(node, ord)
+        JOIN dividend_merkle_trees t ON t.distribution_id = $1
+        ORDER BY p.ord
+        "#,
+    )
+    .bind(distribution_id)
+    .bind(&nodes)
+    .bind(&orders)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| internal(format!("failed to read proof nodes: {}", e)))?;
+    if proof_nodes.len() != nodes.len() || proof_nodes.iter().any(|n| n.len() != 32) {
+        return Err(internal("stored tree is missing proof nodes".to_string()));
+    }
+    let proof: Vec<String> = proof_nodes.iter().map(|n| format!("0x{}", hex::encode(n))).collect();
+
+    let ordered: Vec<&MultiProofClaimRow> = layout.leaves.iter().map(|i| by_tree_index[i]).collect();
+    let proven: Vec<ClaimLeaf> = ordered
+        .iter()
+        .map(|claim| ClaimLeaf {
+            wallet_address: claim.wallet_address.clone(),
+            amount_wei: (claim.amount_usdc * rust_decimal::Decimal::from(1_000_000)).round().to_string(),
+        })
+        .collect();
+
+    // Check the multiproof as `multiProofVerify` will before handing it out
+    let valid = verify_multiproof(&schema, &merkle_root, &proven, &proof, &layout.proof_flags).unwrap_or(false);
+    if !valid {
+        return Err(internal("does not verify against its root".to_string()));
+    }
+
+    let leaves = ordered
+        .iter()
+        .zip(proven)
+        .map(|(claim, leaf)| MultiProofLeaf {
+            claim_id: claim.id,
+            wallet_address: leaf.wallet_address,
+            amount_wei: leaf.amount_wei,
+        })
+        .collect();
+
+    Ok(DistributionMultiProof {
+        distribution_id,
+        merkle_root,
+        leaves,
+        proof,
+        proof_flags: layout.proof_flags,
+    })
+}
+
+/// Request body for checking a distribution attestation.
+#[derive(serde::Deserialize)]
+struct VerifyAttestationRequest {
+    attestation: DistributionAttestation,
+    signature: String,
+}
+
+#[derive(serde::Serialize)]
+struct VerifyAttestationResponse {
+    distribution_id: Uuid,
+    /// Address recovered from the signature.
+    signer: String,
+    /// Key that attested the distribution when it was created.
+    expected_signer: Option<String>,
+    /// Signature is from `expected_signer` and every field matches the stored distribution.
+    valid: bool,
+}
+
+/// Amount deposited for a distribution, which is what its attestation signs:
+/// the revenue plus any carry-forward balances released, less any amounts
+/// carried forward.
+fn deposited_usdc(
+    revenue_usdc: rust_decimal::Decimal,
+    breakdown: Option<&RevenueBreakdown>,
+) -> rust_decimal::Decimal {
+    match breakdown {
+        Some(b) => revenue_usdc + b.carry_forward_released_usdc - b.carried_forward_usdc,
+        None => revenue_usdc,
+    }
+}
+
+/// The stored facts an attestation is checked against.
+#[derive(sqlx::FromRow)]
+struct AttestedDistribution {
+    invention_id: String,
+    merkle_root: String,
+    total_revenue_usdc: rust_decimal::Decimal,
+    revenue_breakdown: Option<sqlx::types::Json<RevenueBreakdown>>,
+    claim_count: i32,
+    attestation_signer: Option<String>,
+    attestation_chain_id: Option<i64>,
+}
+
+/// POST /api/v1/vault/dividends/verify-attestation
+/// Check an EIP-712 distribution attestation against the distribution the Vault recorded.
+async fn verify_attestation(
+    State(pool): State<PgPool>,
+    Json(req): Json<VerifyAttestationRequest>,
+) -> Result<Json<VerifyAttestationResponse>, axum::http::StatusCode> {
+    use rust_decimal::Decimal;
+
+    let distribution_id = Uuid::parse_str(&req.attestation.distribution_id)
+        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+
+    let recorded = sqlx::query_as::<_, AttestedDistribution>(
+        r#"
+        SELECT invention_id, COALESCE(merkle_root, $2) AS merkle_root, total_revenue_usdc, revenue_breakdown, claim_count,
+               attestation_signer, attestation_chain_id
+        FROM dividend_distributions WHERE id = $1
+        "#,
+    )
+    .bind(distribution_id)
+    .bind(NO_MERKLE_ROOT)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    let signer = req.attestation.recover(&req.signature).map_err(|e| {
+        tracing::warn!("Malformed attestation verification request: {}", e);
+        axum::http::StatusCode::BAD_REQUEST
+    })?;
+    let signer = format!("{:#x}", signer);
+
+    let total_usdc = deposited_usdc(recorded.total_revenue_usdc, recorded.revenue_breakdown.as_deref());
+    let expected_signer = recorded.attestation_signer;
+    let attested = recorded.attestation_chain_id.map(|chain_id| DistributionAttestation {
+        invention_id: recorded.invention_id,
+        distribution_id: distribution_id.to_string(),
+        merkle_root: recorded.merkle_root,
+        total_amount: (total_usdc * Decimal::from(1_000_000)).round().to_string(),
+        claim_count: recorded.claim_count as u64,
+        chain_id: chain_id as u64,
+    });
+
+    let valid = expected_signer.as_deref() == Some(signer.as_str())
+        && attested.as_ref() == Some(&req.attestation);
+
+    Ok(Json(VerifyAttestationResponse {
+        distribution_id,
+        signer,
+        expected_signer,
+        valid,
+    }))
+}
+
+/// Request body for recording an on-chain voucher redemption.
+#[derive(serde::Deserialize)]
+struct RedeemVoucherRequest {
+    tx_hash: String,
+}
+
+/// Check that `tx_hash` redeemed `claim`'s voucher from the DividendVault epoch of its
+/// distribution. A transaction that is not confirmed yet is a `409`; one that reverted
+/// or redeemed something else is a `422`.
+async fn verify_voucher_redemption(
+    pool: &PgPool,
+    claim: &DividendClaim,
+    tx_hash: &str,
+) -> Result<(), axum::http::StatusCode> {
+    use ethers::types::{Address, H256, U256};
+    use chain_watcher::{VoucherRedeemedEvent, VoucherRedemption};
+
+    let claim_id = claim.id;
+    let voucher = &claim.voucher.as_ref().ok_or(axum::http::StatusCode::NOT_FOUND)?.0;
+
+    let funded: (Option<i64>, Option<String>) = sqlx::query_as(
+        "SELECT epoch, dividend_vault_address FROM dividend_distributions WHERE id = $1",
+    )
+    .bind(claim.distribution_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load distribution of voucher {}: {}", claim_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let (Some(epoch), Some(vault)) = funded else {
+        tracing::warn!("Voucher {} belongs to a distribution without a DividendVault epoch", claim_id);
+        return Err(axum::http::StatusCode::CONFLICT);
+    };
+
+    let invalid = |e: String| {
+        tracing::error!("Voucher {} cannot be checked: {}", claim_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+    let vault: Address = vault.parse().map_err(|e| invalid(format!("vault address: {}", e)))?;
+    let expected = VoucherRedeemedEvent {
+        epoch: epoch as u64,
+        recipient: voucher.message.recipient.parse().map_err(|e| invalid(format!("recipient: {}", e)))?,
+        nonce: U256::from_dec_str(&voucher.message.nonce).map_err(|e| invalid(format!("nonce: {}", e)))?,
+        amount: U256::from_dec_str(&voucher.message.amount).map_err(|e| invalid(format!("amount: {}", e)))?,
+        signer: voucher.signer.parse().map_err(|e| invalid(format!("signer: {}", e)))?,
+    };
+    if voucher.message.epoch != expected.epoch {
+        tracing::warn!("Voucher {} was signed for epoch {}, not {}", claim_id, voucher.message.epoch, epoch);
+        return Err(axum::http::StatusCode::CONFLICT);
+    }
+
+    let tx_hash: H256 = tx_hash.parse().map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    let rpc_url = std::env::var("RPC_URL").unwrap_or_else(|_| "http://127.0.0.1:8545".to_string());
+    let redemption = chain_watcher::fetch_voucher_redemption(&rpc_url, tx_hash, vault)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch redemption {:?} of voucher {}: {}", tx_hash, claim_id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    match redemption {
+        VoucherRedemption::Pending => {
+            tracing::warn!("Redemption {:?} of voucher {} is not confirmed yet", tx_hash, claim_id);
+            Err(axum::http::StatusCode::CONFLICT)
+        }
+        VoucherRedemption::Reverted => {
+            tracing::warn!("Redemption {:?} of voucher {} reverted", tx_hash, claim_id);
+            Err(axum::http::StatusCode::UNPROCESSABLE_ENTITY)
+        }
+        VoucherRedemption::Confirmed(events) if events.contains(&expected) => Ok(()),
+        VoucherRedemption::Confirmed(_) => {
+            tracing::warn!("{:?} did not redeem voucher {} from {:#x}", tx_hash, claim_id, vault);
+            Err(axum::http::StatusCode::UNPROCESSABLE_ENTITY)
+        }
+    }
+}
+
+/// POST /api/v1/vault/dividends/vouchers/:claim_id/redeem
+/// Record that a voucher was redeemed on-chain. The transaction must have succeeded at
+/// least 12 blocks deep and emitted a `VoucherRedeemed` event from the distribution's
+/// DividendVault whose epoch, recipient, nonce, amount and signer match the voucher.
+/// Repeating the call with the same transaction is a no-op; a different transaction for
+/// the same voucher is a conflict.
+async fn redeem_voucher(
+    State(pool): State<PgPool>,
+    Path(claim_id): Path<Uuid>,
+    Json(req): Json<RedeemVoucherRequest>,
+) -> Result<Json<DividendClaim>, axum::http::StatusCode> {
+    let tx_hash: ethers::types::H256 = req
+        .tx_hash
+        .parse()
+        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    let tx_hash = format!("{:?}", tx_hash);
+
+    let existing = sqlx::query_as::<_, DividendClaim>(
+        "SELECT * FROM dividend_claims WHERE id = $1 AND voucher IS NOT NULL",
+    )
+    .bind(claim_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    if existing.claimed {
+        return if existing.claim_tx_hash.as_deref() == Some(tx_hash.as_str()) {
+            Ok(Json(existing))
+        } else {
+            Err(axum::http::StatusCode::CONFLICT)
+        };
+    }
+
+    verify_voucher_redemption(&pool, &existing, &tx_hash).await?;
+
+    let claim = sqlx::query_as::<_, DividendClaim>(
+        r#"
+        UPDATE dividend_claims SET claimed = true, claim_tx_hash = $2, claimed_at = NOW()
+        WHERE id = $1 AND claimed = false
+        RETURNING *
+        "#,
+    )
+    .bind(claim_id)
+    .bind(&tx_hash)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to record voucher redemption for {}: {}", claim_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    // Lost a race with another redemption
+    .ok_or(axum::http::StatusCode::CONFLICT)?;
+
+    let audit_payload = serde_json::json!({
+        "claim_id": claim_id,
+        "distribution_id": claim.distribution_id,
+        "amount_usdc": claim.amount_usdc.to_string(),
+        "tx_hash": tx_hash,
+    });
+
+    sqlx::query(
+        "INSERT INTO audit_logs (event_type, actor, target_resource, payload, created_at) VALUES ($1, $2, $3, $4, NOW())"
+    )
+    .bind("DIVIDEND_VOUCHER_REDEEMED")
+    .bind(&claim.wallet_address)
+    .bind(claim.distribution_id.to_string())
+    .bind(&audit_payload)
+    .execute(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to write audit log: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    claim_expiry::flag_claimed_after_expiry(&mut conn, claim_id, &tx_hash)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check expiry of voucher {}: {}", claim_id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!("Voucher {} redeemed in {}", claim_id, tx_hash);
+
+    Ok(Json(claim))
+}
+
+#[derive(serde::Deserialize)]
+struct UnclaimedQuery {
+    policy: Option<UnclaimedPolicy>,
+}
+
+/// GET /api/v1/vault/dividends/unclaimed/:invention_id
+/// The invention's expired claims, optionally filtered by policy (e.g. `ESCHEAT` for reporting).
+async fn get_unclaimed(
+    State(pool): State<PgPool>,
+    Path(invention_id): Path<String>,
+    Query(query): Query<UnclaimedQuery>,
+) -> Result<Json<UnclaimedReport>, axum::http::StatusCode> {
+    let entries = sqlx::query_as::<_, UnclaimedDividend>(
+        "SELECT * FROM unclaimed_dividends
+         WHERE invention_id = $1 AND ($2::TEXT IS NULL OR policy = $2)
+         ORDER BY expired_at, claim_id",
+    )
+    .bind(&invention_id)
+    .bind(query.policy.map(|p| p.as_str()))
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load unclaimed dividends for {}: {}", invention_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(UnclaimedReport {
+        total_usdc: entries.iter().map(|e| e.amount_usdc).sum(),
+        invention_id,
+        entries,
+    }))
+}
+
+/// Page size of the listing endpoints when the request does not set `limit`.
+const DEFAULT_PAGE_SIZE: i64 = 50;
+
+/// Largest `limit` the listing endpoints accept.
+const MAX_PAGE_SIZE: i64 = 200;
+
+/// Validate `limit` and `offset` query parameters, applying the default page size.
+fn page_bounds(limit: Option<i64>, offset: Option<i64>) -> Result<(i64, i64), axum::http::StatusCode> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    let offset = offset.unwrap_or(0);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) || offset < 0 {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    Ok((limit, offset))
+}
+
+/// A `dividend_distributions` row with everything the read endpoints return.
+#[derive(sqlx::FromRow)]
+struct DistributionRow {
+    id: Uuid,
+    invention_id: String,
+    total_revenue_usdc: rust_decimal::Decimal,
+    merkle_root: Option<String>,
+    claim_count: i32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    status: String,
+    epoch: Option<i64>,
+    publish_tx_hash: Option<String>,
+    payout_method: String,
+    voucher_deadline: Option<i64>,
+    mode: String,
+    snapshot_block: Option<i64>,
+    leaf_schema: sqlx::types::Json<StandardLeafSchema>,
+    dust_usdc: rust_decimal::Decimal,
+    dust_recipients: sqlx::types::Json<Vec<String>>,
+    revenue_breakdown: Option<sqlx::types::Json<RevenueBreakdown>>,
+    attestation_signer: Option<String>,
+    attestation_signature: Option<String>,
+    attestation_chain_id: Option<i64>,
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+    dividend_vault_address: Option<String>,
+}
+
+const DISTRIBUTION_COLUMNS: &str = "id, invention_id, total_revenue_usdc, merkle_root, claim_count, created_at, status, epoch,
+     publish_tx_hash, payout_method, voucher_deadline, mode, snapshot_block, leaf_schema, dust_usdc, dust_recipients, revenue_breakdown,
+     attestation_signer, attestation_signature, attestation_chain_id, published_at, dividend_vault_address";
+
+impl DistributionRow {
+    fn into_distribution(self) -> anyhow::Result<DividendDistribution> {
+        use rust_decimal::Decimal;
+
+        let revenue_breakdown = self.revenue_breakdown.map(|b| b.0);
+
+        let dust = (self.dust_usdc > Decimal::ZERO).then(|| DustAllocation {
+            amount_wei: (self.dust_usdc * Decimal::from(1_000_000)).round().to_string(),
+            recipients: self.dust_recipients.0,
+        });
+
+        // The signed message is rebuilt from the stored facts, as in verify-attestation
+        let attestation = match (self.attestation_signer, self.attestation_signature, self.attestation_chain_id) {
+            (Some(signer), Some(signature), Some(chain_id)) => {
+                let total_usdc = deposited_usdc(self.total_revenue_usdc, revenue_breakdown.as_ref());
+                Some(SignedAttestation {
+                    message: DistributionAttestation {
+                        invention_id: self.invention_id.clone(),
+                        distribution_id: self.id.to_string(),
+                        merkle_root: self.merkle_root.clone().unwrap_or_else(|| NO_MERKLE_ROOT.to_string()),
+                        total_amount: (total_usdc * Decimal::from(1_000_000)).round().to_string(),
+                        claim_count: self.claim_count as u64,
+                        chain_id: chain_id as u64,
+                    },
+                    signer,
+                    signature,
+                })
+            }
+            _ => None,
+        };
+
+        Ok(DividendDistribution {
+            id: self.id,
+            invention_id: self.invention_id,
+            total_revenue_usdc: self.total_revenue_usdc,
+            merkle_root: self.merkle_root,
+            claim_count: self.claim_count,
+            created_at: self.created_at,
+            status: self.status.parse()?,
+            epoch: self.epoch,
+            publish_tx_hash: self.publish_tx_hash,
+            payout_method: self.payout_method.parse()?,
+            voucher_deadline: self.voucher_deadline,
+            mode: self.mode.parse()?,
+            snapshot_block: self.snapshot_block,
+            leaf_schema: self.leaf_schema.0,
+            // Only recorded in the audit log
+            merged_leaves: Vec::new(),
+            revenue_breakdown,
+            dust,
+            attestation,
+        })
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct DistributionListQuery {
+    status: Option<DistributionStatus>,
+    /// Only distributions created at or after this time.
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only distributions created before this time.
+    to: Option<chrono::DateTime<chrono::Utc>>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+/// GET /api/v1/vault/dividends/by-invention/:invention_id?status=&from=&to=&limit=&offset=
+/// The invention's distributions, newest first.
+async fn list_distributions(
+    State(pool): State<PgPool>,
+    Path(invention_id): Path<String>,
+    Query(query): Query<DistributionListQuery>,
+) -> Result<Json<DistributionPage>, axum::http::StatusCode> {
+    let (limit, offset) = page_bounds(query.limit, query.offset)?;
+
+    let filter = "invention_id = $1 AND ($2::TEXT IS NULL OR status = $2)
+         AND ($3::TIMESTAMPTZ IS NULL OR created_at >= $3) AND ($4::TIMESTAMPTZ IS NULL OR created_at < $4)";
+    let status = query.status.map(|s| s.as_str());
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM dividend_distributions WHERE {}", filter))
+        .bind(&invention_id)
+        .bind(status)
+        .bind(query.from)
+        .bind(query.to)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to count distributions of {}: {}", invention_id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let rows = sqlx::query_as::<_, DistributionRow>(&format!(
+        "SELECT {} FROM dividend_distributions WHERE {} ORDER BY created_at DESC, id LIMIT $5 OFFSET $6",
+        DISTRIBUTION_COLUMNS, filter
+    ))
+    .bind(&invention_id)
+    .bind(status)
+    .bind(query.from)
+    .bind(query.to)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to list distributions of {}: {}", invention_id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let distributions = rows
+        .into_iter()
+        .map(DistributionRow::into_distribution)
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(|e| {
+            tracing::error!("Unreadable distribution of {}: {}", invention_id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(DistributionPage {
+        invention_id,
+        total,
+        limit,
+        offset,
+        distributions,
+    }))
+}
+
+/// GET /api/v1/vault/dividends/distributions/:id
+/// A distribution with its fee breakdown, root, epoch and claim totals.
+async fn get_distribution(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<DistributionDetail>, axum::http::StatusCode> {
+    let row = sqlx::query_as::<_, DistributionRow>(&format!(
+        "SELECT {} FROM dividend_distributions WHERE id = $1",
+        DISTRIBUTION_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load distribution {}: {}", id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    let claims = sqlx::query_as::<_, ClaimTotals>(
+        r#"
+        SELECT COUNT(*) AS total_count,
+               COALESCE(SUM(amount_usdc), 0) AS total_usdc,
+               COUNT(*) FILTER (WHERE claimed) AS claimed_count,
+               COALESCE(SUM(amount_usdc) FILTER (WHERE claimed), 0) AS claimed_usdc,
+               COUNT(*) FILTER (WHERE NOT claimed AND expired_at IS NULL) AS unclaimed_count,
+               COALESCE(SUM(amount_usdc) FILTER (WHERE NOT claimed AND expired_at IS NULL), 0) AS unclaimed_usdc,
+               COUNT(*) FILTER (WHERE NOT claimed AND expired_at IS NOT NULL) AS expired_count,
+               COALESCE(SUM(amount_usdc) FILTER (WHERE NOT claimed AND expired_at IS NOT NULL), 0) AS expired_usdc
+        FROM dividend_claims WHERE distribution_id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to total claims of distribution {}: {}", id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let published_at = row.published_at;
+    let dividend_vault_address = row.dividend_vault_address.clone();
+    let distribution = row.into_distribution().map_err(|e| {
+        tracing::error!("Unreadable distribution {}: {}", id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(DistributionDetail {
+        distribution,
+        published_at,
+        dividend_vault_address,
+        claims,
+    }))
+}
+
+#[derive(serde::Deserialize)]
+struct ClaimListQuery {
+    /// Only claimed (`true`) or unclaimed (`false`) claims.
+    claimed: Option<bool>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+/// GET /api/v1/vault/dividends/distributions/:id/claims?claimed=&limit=&offset=
+/// A distribution's claims by wallet address, with proofs for Merkle claims.
+async fn list_distribution_claims(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ClaimListQuery>,
+) -> Result<Json<ClaimPage>, axum::http::StatusCode> {
+    let (limit, offset) = page_bounds(query.limit, query.offset)?;
+
+    let epoch: Option<Option<i64>> = sqlx::query_scalar("SELECT epoch FROM dividend_distributions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load distribution {}: {}", id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let epoch = epoch.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM dividend_claims WHERE distribution_id = $1 AND ($2::BOOLEAN IS NULL OR claimed = $2)",
+    )
+    .bind(id)
+    .bind(query.claimed)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to count claims of distribution {}: {}", id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut claims = sqlx::query_as::<_, DividendClaim>(
+        r#"
+        SELECT * FROM dividend_claims
+        WHERE distribution_id = $1 AND ($2::BOOLEAN IS NULL OR claimed = $2)
+        ORDER BY wallet_address, id
+        LIMIT $3 OFFSET $4
+        "#,
+    )
+    .bind(id)
+    .bind(query.claimed)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to list claims of distribution {}: {}", id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    for claim in &mut claims {
+        claim.epoch = epoch;
+    }
+    attach_stored_proofs(&pool, &mut claims).await?;
+
+    Ok(Json(ClaimPage {
+        distribution_id: id,
+        total,
+        limit,
+        offset,
+        claims,
+    }))
+}
+
+#[derive(serde::Deserialize)]
+struct TransitionRequest {
+    reason: Option<String>,
+}
+
+/// POST /api/v1/vault/dividends/distributions/:id/approve
+/// Approve a draft distribution for publication.
+async fn approve_distribution(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    AuthenticatedActor(actor): AuthenticatedActor,
+    req: Option<Json<TransitionRequest>>,
+) -> Result<Json<DistributionStatusChange>, axum::http::StatusCode> {
+    change_status(&pool, id, DistributionStatus::Approved, &actor, req.and_then(|Json(r)| r.reason)).await
+}
+
+/// POST /api/v1/vault/dividends/distributions/:id/publish
+/// Publish an approved distribution. Per-epoch Merkle roots are posted to the
+/// DividendVault first, and voucher distributions have their epoch funded and their
+/// vouchers signed. Cumulative distributions are refused: nothing posts their root to
+/// a cumulative distributor yet.
+async fn publish_distribution(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    AuthenticatedActor(actor): AuthenticatedActor,
+    req: Option<Json<TransitionRequest>>,
+) -> Result<Json<DistributionStatusChange>, axum::http::StatusCode> {
+    let kind: Option<(String, String, String, Option<i64>)> = sqlx::query_as(
+        "SELECT status, payout_method, mode, voucher_deadline FROM dividend_distributions WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load distribution {}: {}", id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let (status, payout_method, mode, voucher_deadline) = kind.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    if mode == DistributionMode::Cumulative.as_str() {
+        tracing::warn!("Distribution {} not published: cumulative roots cannot be published on-chain", id);
+        return Err(axum::http::StatusCode::CONFLICT);
+    }
+
+    let vouchers = payout_method == PayoutMethod::Voucher.as_str();
+    if vouchers {
+        // Refuse before funding an epoch no voucher could be signed or redeemed for
+        match signer_from_env() {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                tracing::warn!("Distribution {} cannot be published: VAULT_ATTESTATION_PRIVATE_KEY not set", id);
+                return Err(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+            }
+            Err(e) => {
+                tracing::error!("Voucher signer misconfigured: {}", e);
+                return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+        // A PUBLISHING distribution may already be funded, so it is always finished
+        let latest_start = voucher_deadline.map(|d| d - VOUCHER_PUBLISH_MARGIN.num_seconds());
+        if status == DistributionStatus::Approved.as_str()
+            && latest_start.is_some_and(|t| t <= chrono::Utc::now().timestamp())
+        {
+            tracing::warn!("Distribution {} not published: its voucher deadline is too close", id);
+            return Err(axum::http::StatusCode::CONFLICT);
+        }
+    }
+
+    let config = PublisherConfig::from_env()
+        .map_err(|e| {
+            tracing::error!("Publisher configuration is invalid: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or_else(|| {
+            tracing::warn!("Distribution {} cannot be published: no publisher key configured", id);
+            axum::http::StatusCode::SERVICE_UNAVAILABLE
+        })?;
+    let rpc_url = std::env::var("RPC_URL")
+        .unwrap_or_else(|_| "http://127.0.0.1:8545".to_string());
+
+    let published = distribution_publisher::publish_distribution(&pool, &rpc_url, &config, id, &actor)
+        .await
+        .map_err(|e| {
+            match &e {
+                distribution_publisher::PublishError::Failed(_) => {
+                    tracing::error!("Publishing distribution {} failed: {}", id, e)
+                }
+                _ => tracing::warn!("Distribution {} not published: {}", id, e),
+            }
+            axum::http::StatusCode::from(e)
+        })?;
+
+    // The audit entry records where the root landed
+    let note = format!("epoch {} in {:?}", published.epoch, published.tx_hash);
+    let reason = Some(match req.and_then(|Json(r)| r.reason) {
+        Some(reason) => format!("{} ({})", reason, note),
+        None => note,
+    });
+    if vouchers {
+        return publish_vouchers(&pool, id, &actor, reason, published.epoch).await;
+    }
+
+    change_status(&pool, id, DistributionStatus::Published, &actor, reason).await
+}
+
+/// Sign the vouchers of voucher distribution `id` against its funded DividendVault
+/// epoch and move it to PUBLISHED in one transaction, so no voucher exists before
+/// publication.
+async fn publish_vouchers(
+    pool: &PgPool,
+    id: Uuid,
+    actor: &str,
+    reason: Option<String>,
+    epoch: u64,
+) -> Result<Json<DistributionStatusChange>, axum::http::StatusCode> {
+    let db_error = |e: sqlx::Error| {
+        tracing::error!("Failed to publish vouchers of distribution {}: {}", id, e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+
+    let mut tx = pool.begin().await.map_err(db_error)?;
+    let to = DistributionStatus::Published;
+    let from = distribution_lifecycle::transition_in(&mut tx, id, to, actor, reason.as_deref())
+        .await
+        .map_err(|e| {
+            tracing::warn!("Transition of {} to {} refused: {}", id, to.as_str(), e);
+            axum::http::StatusCode::from(e)
+        })?;
+
+    let deadline: Option<i64> = sqlx::query_scalar("SELECT voucher_deadline FROM dividend_distributions WHERE id = $1")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    let now = chrono::Utc::now();
+    let deadline = deadline.unwrap_or_else(|| (now + VOUCHER_VALIDITY).timestamp());
+    if deadline <= now.timestamp() {
+        tracing::warn!("Distribution {} not published: its voucher deadline has passed", id);
+        return Err(axum::http::StatusCode::CONFLICT);
+    }
+
+    let claims: Vec<(Uuid, String, rust_decimal::Decimal)> = sqlx::query_as(
+        "SELECT id, wallet_address, amount_usdc FROM dividend_claims WHERE distribution_id = $1 ORDER BY id",
+    )
+    .bind(id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    let vouchers = sign_vouchers(id, &claims, deadline as u64, epoch)?;
+    for (claim_id, voucher) in &vouchers {
+        sqlx::query("UPDATE dividend_claims SET voucher = $2 WHERE id = $1")
+            .bind(claim_id)
+            .bind(sqlx::types::Json(voucher))
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+    }
+    tx.commit().await.map_err(db_error)?;
+
+    tracing::info!("Distribution {} published with {} vouchers by {}", id, vouchers.len(), actor);
+    Ok(Json(DistributionStatusChange { distribution_id: id, from, to }))
+}
+
+/// POST /api/v1/vault/dividends/distributions/:id/close
+/// End a published distribution's claim period.
+async fn close_distribution(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    AuthenticatedActor(actor): AuthenticatedActor,
+    req: Option<Json<TransitionRequest>>,
+) -> Result<Json<DistributionStatusChange>, axum::http::StatusCode> {
+    change_status(&pool, id, DistributionStatus::Closed, &actor, req.and_then(|Json(r)| r.reason)).await
+}
+
+/// POST /api/v1/vault/dividends/distributions/:id/cancel
+/// Withdraw a distribution that has not been published.
+async fn cancel_distribution(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    AuthenticatedActor(actor): AuthenticatedActor,
+    req: Option<Json<TransitionRequest>>,
+) -> Result<Json<DistributionStatusChange>, axum::http::StatusCode> {
+    change_status(&pool, id, DistributionStatus::Cancelled, &actor, req.and_then(|Json(r)| r.reason)).await
+}
+
+/// Apply a lifecycle transition requested by the authenticated `actor`.
+async fn change_status(
+    pool: &PgPool,
+    id: Uuid,
+    to: DistributionStatus,
+    actor: &str,
+    reason: Option<String>,
+) -> Result<Json<DistributionStatusChange>, axum::http::StatusCode> {
+    let from = distribution_lifecycle::transition(pool, id, to, actor, reason.as_deref())
+        .await
+        .map_err(|e| {
+            tracing::warn!("Transition of {} to {} refused: {}", id, to.as_str(), e);
+            axum::http::StatusCode::from(e)
+        })?;
+
+    Ok(Json(DistributionStatusChange { distribution_id: id, from, to }))
+}
@@ -2,6 +2,7 @@
 
 use axum::{
     extract::{Path, State},
+    http::HeaderMap,
     routing::{get, post},
     Json, Router,
 };
@@ -9,6 +10,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::models::investment::{Investment, InvestmentStatus, VerifyRequest};
+use crate::services::idempotency::run_idempotent;
 use crate::services::transaction_verifier;
 use crate::services::pubsub::{InvestmentConfirmedMessage, PubSubClient};
 
@@ -22,10 +24,19 @@ pub fn router(pool: PgPool) -> Router {
 
 /// POST /api/v1/vault/investments/verify
 /// Verify a blockchain transaction and record the confirmed investment.
+/// Retries with the same `Idempotency-Key` get the recorded investment back.
 async fn verify_transaction(
     State(pool): State<PgPool>,
+    headers: HeaderMap,
     Json(req): Json<VerifyRequest>,
 ) -> Result<Json<Investment>, axum::http::StatusCode> {
+    run_idempotent(&pool, &headers, "investments.verify", &req, |_| record_investment(&pool, &req))
+        .await
+        .map(Json)
+}
+
+/// Verify `req` on-chain, store the investment and announce it.
+async fn record_investment(pool: &PgPool, req: &VerifyRequest) -> Result<Investment, axum::http::StatusCode> {
     tracing::info!("Verifying transaction: {}", req.tx_hash);
 
     let rpc_url = std::env::var("RPC_URL")
@@ -71,7 +82,7 @@ async fn verify_transaction(
     .bind(InvestmentStatus::Confirmed)
     .bind(verification.block_number as i64)
     .bind(verification.token_amount)
-    .fetch_one(&pool)
+    .fetch_one(pool)
     .await
     .map_err(|e| {
         tracing::error!("Failed to insert investment: {}", e);
@@ -95,7 +106,7 @@ async fn verify_transaction(
         }
     }
 
-    Ok(Json(investment))
+    Ok(investment)
 }
 
 /// GET /api/v1/vault/investments/:id
@@ -1,13 +1,19 @@
 //! API Routes for the Vault service.
 
+pub mod compliance;
 pub mod investments;
 pub mod dividends;
+pub mod tokens;
 
-use axum::Router;
+use axum::{middleware::from_fn, Router};
 use sqlx::PgPool;
 
+use crate::middleware::require_service_auth;
+
 pub fn vault_router(pool: PgPool) -> Router {
     Router::new()
         .nest("/investments", investments::router(pool.clone()))
-        .nest("/dividends", dividends::router(pool))
+        .nest("/dividends", dividends::router(pool.clone()).layer(from_fn(require_service_auth)))
+        .nest("/tokens", tokens::router(pool.clone()))
+        .nest("/compliance", compliance::router(pool).layer(from_fn(require_service_auth)))
 }
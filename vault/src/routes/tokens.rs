@@ -0,0 +1,126 @@
+//! RoyaltyToken ownership routes.
+//! Answers balance and holder-count queries from the Transfer index.
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use ethers::types::Address;
+use sqlx::PgPool;
+
+use crate::models::token::{HolderCountPoint, TokenBalances, TokenHolderBalance};
+use crate::services::token_indexer;
+
+pub fn router(pool: PgPool) -> Router {
+    Router::new()
+        .route("/:token_address/balances", get(get_balances))
+        .route("/:token_address/holder-count", get(get_holder_count))
+        .with_state(pool)
+}
+
+#[derive(serde::Deserialize)]
+struct BalancesQuery {
+    /// Defaults to the last indexed block.
+    block: Option<u64>,
+}
+
+#[derive(serde::Deserialize)]
+struct HolderCountQuery {
+    #[serde(default)]
+    from_block: u64,
+    /// Defaults to the last indexed block.
+    to_block: Option<u64>,
+}
+
+/// GET /api/v1/vault/tokens/:token_address/balances?block=N
+/// Balances of every holder of the token at the end of block N.
+async fn get_balances(
+    State(pool): State<PgPool>,
+    Path(token_address): Path<String>,
+    Query(query): Query<BalancesQuery>,
+) -> Result<Json<TokenBalances>, axum::http::StatusCode> {
+    let token = parse_token(&token_address)?;
+    let indexed_through = fetch_indexed_through(&pool, &token).await?;
+
+    let block_number = query.block.unwrap_or(indexed_through);
+    if block_number > indexed_through {
+        return Err(axum::http::StatusCode::CONFLICT);
+    }
+
+    let balances = token_indexer::balances_at_block(&pool, &token, block_number)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load balances of {} at block {}: {}", token, block_number, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(axum::http::StatusCode::CONFLICT)?;
+
+    let holders: Vec<TokenHolderBalance> = balances
+        .into_iter()
+        .map(|(wallet, balance)| TokenHolderBalance {
+            wallet_address: format!("{:#x}", wallet),
+            balance_wei: balance.to_string(),
+        })
+        .collect();
+
+    Ok(Json(TokenBalances {
+        token_address: token,
+        block_number,
+        indexed_through,
+        holder_count: holders.len(),
+        holders,
+    }))
+}
+
+/// GET /api/v1/vault/tokens/:token_address/holder-count?from_block=A&to_block=B
+/// Holder count after every block in the range that changed balances.
+async fn get_holder_count(
+    State(pool): State<PgPool>,
+    Path(token_address): Path<String>,
+    Query(query): Query<HolderCountQuery>,
+) -> Result<Json<Vec<HolderCountPoint>>, axum::http::StatusCode> {
+    let token = parse_token(&token_address)?;
+    let indexed_through = fetch_indexed_through(&pool, &token).await?;
+
+    let to_block = query.to_block.unwrap_or(indexed_through).min(indexed_through);
+    if query.from_block > to_block {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    let counts = token_indexer::holder_counts(&pool, &token, query.from_block, to_block)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load holder counts of {}: {}", token, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(
+        counts
+            .into_iter()
+            .map(|(block_number, holder_count)| HolderCountPoint {
+                block_number,
+                holder_count,
+            })
+            .collect(),
+    ))
+}
+
+/// Validate a token address and return it in the index's lowercase form.
+fn parse_token(token_address: &str) -> Result<String, axum::http::StatusCode> {
+    let token: Address = token_address
+        .parse()
+        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    Ok(format!("{:#x}", token))
+}
+
+/// Last indexed block of `token`; tokens that were never indexed are not found.
+async fn fetch_indexed_through(pool: &PgPool, token: &str) -> Result<u64, axum::http::StatusCode> {
+    token_indexer::indexed_through(pool, token)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load index checkpoint of {}: {}", token, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(axum::http::StatusCode::NOT_FOUND)
+}
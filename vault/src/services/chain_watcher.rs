@@ -3,14 +3,21 @@
 //! Listens to EVM events from the Crowdsale and DividendVault contracts.
 //! When a transaction is confirmed on-chain, publishes `investment.confirmed`
 //! to Pub/Sub so the TypeScript backend can update Firestore.
+//! Also indexes `Claimed` events from cumulative distributors to track claimed-to-date amounts,
+//! and `DividendClaimed` events from the DividendVault to mark per-epoch claims as paid.
+//! Voucher redemptions are not indexed; their `VoucherRedeemed` events are checked
+//! per transaction when a redemption is reported.
 
 use anyhow::Result;
 use ethers::prelude::*;
 use ethers::abi::{self, Token};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
+use crate::services::claim_expiry;
 use crate::services::pubsub::{PubSubClient, InvestmentConfirmedMessage};
 
 /// Watch for Investment events on the Crowdsale contract.
@@ -161,6 +168,532 @@ async fn record_investment(
     Ok(())
 }
 
+/// How often cumulative distributors and the DividendVault are polled for new claim events.
+const CLAIM_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Blocks behind the chain head before claim events are indexed.
+const CLAIM_CONFIRMATIONS: u64 = 12;
+
+/// Blocks requested per `eth_getLogs` call when catching up on claim events.
+const CLAIM_LOG_CHUNK: u64 = 10_000;
+
+/// Index `Claimed(address indexed account, uint256 amount)` events from each invention's
+/// cumulative distributor and keep `dividend_claimed_to_date` up to date.
+///
+/// Distributor addresses are re-read from `invention_ledger` on every poll, so newly
+/// configured inventions are picked up without a restart. Each distributor is indexed
+/// up to `CLAIM_CONFIRMATIONS` behind the head with its progress checkpointed in
+/// `cumulative_distributor_checkpoints`; a new distributor is indexed from genesis.
+pub async fn watch_cumulative_claims(rpc_url: &str, pool: sqlx::PgPool) -> Result<()> {
+    let provider = Provider::<Http>::try_from(rpc_url)?;
+    let mut interval = tokio::time::interval(CLAIM_POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let distributors: Vec<(String, String)> = match sqlx::query_as(
+            "SELECT invention_id, cumulative_distributor_address FROM invention_ledger WHERE cumulative_distributor_address IS NOT NULL",
+        )
+        .fetch_all(&pool)
+        .await
+        {
+            Ok(distributors) => distributors,
+            Err(e) => {
+                tracing::warn!("Failed to load cumulative distributors: {}", e);
+                continue;
+            }
+        };
+
+        let mut inventions: HashMap<Address, String> = HashMap::new();
+        for (invention_id, address) in distributors {
+            match address.parse::<Address>() {
+                Ok(address) => {
+                    inventions.insert(address, invention_id);
+                }
+                Err(e) => tracing::warn!("Invalid distributor address for {}: {}", invention_id, e),
+            }
+        }
+        if inventions.is_empty() {
+            continue;
+        }
+
+        let head = match provider.get_block_number().await {
+            Ok(head) => head.as_u64(),
+            Err(e) => {
+                tracing::warn!("Failed to fetch block number: {}", e);
+                continue;
+            }
+        };
+        let Some(safe_head) = head.checked_sub(CLAIM_CONFIRMATIONS) else {
+            continue;
+        };
+
+        for (distributor, invention_id) in &inventions {
+            // The failed chunk is retried from the checkpoint on the next poll
+            if let Err(e) = index_cumulative_claims(&provider, &pool, *distributor, invention_id, safe_head).await {
+                tracing::error!("Failed to index cumulative claims for {}: {}", invention_id, e);
+            }
+        }
+    }
+}
+
+/// Index one distributor's `Claimed` events from its checkpoint through `safe_head`.
+async fn index_cumulative_claims(
+    provider: &Provider<Http>,
+    pool: &sqlx::PgPool,
+    distributor: Address,
+    invention_id: &str,
+    safe_head: u64,
+) -> Result<()> {
+    let distributor_key = format!("{:#x}", distributor);
+
+    let checkpoint: Option<i64> = sqlx::query_scalar(
+        "SELECT last_indexed_block FROM cumulative_distributor_checkpoints WHERE distributor_address = $1",
+    )
+    .bind(&distributor_key)
+    .fetch_optional(pool)
+    .await?;
+
+    let mut from = checkpoint.map(|b| b as u64 + 1).unwrap_or(0);
+    while from <= safe_head {
+        let to = (from + CLAIM_LOG_CHUNK - 1).min(safe_head);
+        let filter = Filter::new()
+            .address(distributor)
+            .event("Claimed(address,uint256)")
+            .from_block(from)
+            .to_block(to);
+
+        let logs = provider
+            .get_logs(&filter)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch Claimed logs {}..{}: {}", from, to, e))?;
+        record_cumulative_claims(pool, &distributor_key, invention_id, &logs, to).await?;
+        from = to + 1;
+    }
+    Ok(())
+}
+
+/// Decode a cumulative distributor `Claimed` log into (account, amount_usdc).
+fn decode_claimed_log(log: &Log) -> Result<(Address, Decimal)> {
+    let account = if log.topics.len() > 1 {
+        Address::from(log.topics[1])
+    } else {
+        return Err(anyhow::anyhow!("Missing account topic in Claimed event"));
+    };
+
+    let tokens = abi::decode(&[abi::ParamType::Uint(256)], &log.data)
+        .map_err(|e| anyhow::anyhow!("ABI decode error: {}", e))?;
+
+    let amount_raw = match &tokens[0] {
+        Token::Uint(v) => *v,
+        _ => return Err(anyhow::anyhow!("Invalid amount type")),
+    };
+
+    // USDC uses 6 decimals
+    let amount_usdc = i128::try_from(amount_raw)
+        .ok()
+        .and_then(|a| Decimal::try_from_i128_with_scale(a, 6).ok())
+        .ok_or_else(|| anyhow::anyhow!("Claimed amount {} is out of range", amount_raw))?;
+
+    Ok((account, amount_usdc))
+}
+
+/// Record a chunk of `Claimed` events, each once, add them to the wallets' claimed-to-date
+/// totals and advance the distributor's checkpoint to `through_block` in one transaction.
+async fn record_cumulative_claims(
+    pool: &sqlx::PgPool,
+    distributor_key: &str,
+    invention_id: &str,
+    logs: &[Log],
+    through_block: u64,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    for log in logs {
+        let (account, amount_usdc) = decode_claimed_log(log)?;
+        let tx_hash = log
+            .transaction_hash
+            .map(|h| format!("{:?}", h))
+            .ok_or_else(|| anyhow::anyhow!("Claimed log without transaction hash"))?;
+        let log_index = log.log_index.map(|i| i.as_u64()).unwrap_or(0);
+        let block_number = log.block_number.map(|b| b.as_u64()).unwrap_or(0);
+        let wallet_address = format!("{:#x}", account);
+
+        let inserted = sqlx::query(
+            "INSERT INTO cumulative_claim_events (tx_hash, log_index, invention_id, wallet_address, amount_usdc, block_number, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, NOW())
+             ON CONFLICT (tx_hash, log_index) DO NOTHING"
+        )
+        .bind(&tx_hash)
+        .bind(log_index as i64)
+        .bind(invention_id)
+        .bind(&wallet_address)
+        .bind(amount_usdc)
+        .bind(block_number as i64)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if inserted == 0 {
+            continue;
+        }
+
+        sqlx::query(
+            "INSERT INTO dividend_claimed_to_date (invention_id, wallet_address, claimed_usdc, last_claim_tx_hash, updated_at)
+             VALUES ($1, $2, $3, $4, NOW())
+             ON CONFLICT (invention_id, wallet_address) DO UPDATE
+             SET claimed_usdc = dividend_claimed_to_date.claimed_usdc + EXCLUDED.claimed_usdc,
+                 last_claim_tx_hash = EXCLUDED.last_claim_tx_hash,
+                 updated_at = NOW()"
+        )
+        .bind(invention_id)
+        .bind(&wallet_address)
+        .bind(amount_usdc)
+        .bind(&tx_hash)
+        .execute(&mut *tx)
+        .await?;
+
+        tracing::info!(
+            "Recorded cumulative claim: invention={}, wallet={}, amount={} USDC, tx={}",
+            invention_id,
+            wallet_address,
+            amount_usdc,
+            tx_hash
+        );
+    }
+
+    sqlx::query(
+        "INSERT INTO cumulative_distributor_checkpoints (distributor_address, last_indexed_block, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (distributor_address) DO UPDATE
+         SET last_indexed_block = EXCLUDED.last_indexed_block, updated_at = NOW()",
+    )
+    .bind(distributor_key)
+    .bind(through_block as i64)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// A decoded `DividendClaimed` event with its position on chain.
+#[derive(Debug, Clone, PartialEq)]
+struct DividendClaimedEvent {
+    tx_hash: String,
+    log_index: u64,
+    block_number: u64,
+    epoch: u64,
+    claimant: Address,
+    amount_usdc: Decimal,
+}
+
+/// Index `DividendClaimed(uint256 indexed epoch, address indexed claimant, uint256 amount)`
+/// events from the DividendVault and mark the matching claims as claimed.
+///
+/// Events are matched to claim rows by epoch and wallet. A paid amount that differs
+/// from the stored claim, or an event without a claim row, is logged as an error and
+/// written to `audit_logs` as `DIVIDEND_CLAIM_MISMATCH`. Progress is checkpointed in
+/// `dividend_vault_checkpoints`; a new vault is indexed from genesis.
+pub async fn watch_dividend_claims(rpc_url: &str, vault_address: &str, pool: sqlx::PgPool) -> Result<()> {
+    let provider = Provider::<Http>::try_from(rpc_url)?;
+    let vault: Address = vault_address.parse()?;
+    let vault_key = format!("{:#x}", vault);
+    let mut interval = tokio::time::interval(CLAIM_POLL_INTERVAL);
+
+    tracing::info!("Indexing DividendClaimed events on {}", vault_key);
+
+    loop {
+        interval.tick().await;
+
+        let head = match provider.get_block_number().await {
+            Ok(head) => head.as_u64(),
+            Err(e) => {
+                tracing::warn!("Failed to fetch block number: {}", e);
+                continue;
+            }
+        };
+        let Some(safe_head) = head.checked_sub(CLAIM_CONFIRMATIONS) else {
+            continue;
+        };
+
+        let checkpoint: Option<i64> = match sqlx::query_scalar(
+            "SELECT last_indexed_block FROM dividend_vault_checkpoints WHERE dividend_vault_address = $1",
+        )
+        .bind(&vault_key)
+        .fetch_optional(&pool)
+        .await
+        {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => {
+                tracing::warn!("Failed to load DividendVault checkpoint: {}", e);
+                continue;
+            }
+        };
+
+        let mut from = checkpoint.map(|b| b as u64 + 1).unwrap_or(0);
+        while from <= safe_head {
+            let to = (from + CLAIM_LOG_CHUNK - 1).min(safe_head);
+            let filter = Filter::new()
+                .address(vault)
+                .event("DividendClaimed(uint256,address,uint256)")
+                .from_block(from)
+                .to_block(to);
+
+            let result = match provider.get_logs(&filter).await {
+                Ok(logs) => match logs.iter().map(decode_dividend_claimed_log).collect::<Result<Vec<_>>>() {
+                    Ok(events) => record_dividend_claims(&pool, &vault_key, &events, to).await,
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(anyhow::anyhow!("Failed to fetch DividendClaimed logs {}..{}: {}", from, to, e)),
+            };
+
+            // The chunk is retried from the checkpoint on the next poll
+            if let Err(e) = result {
+                tracing::error!("Failed to index DividendVault claims: {}", e);
+                break;
+            }
+            from = to + 1;
+        }
+    }
+}
+
+/// Decode a DividendVault `DividendClaimed` log.
+fn decode_dividend_claimed_log(log: &Log) -> Result<DividendClaimedEvent> {
+    if log.topics.len() != 3 {
+        return Err(anyhow::anyhow!("DividendClaimed event must have 3 topics, got {}", log.topics.len()));
+    }
+    if log.data.len() != 32 {
+        return Err(anyhow::anyhow!("DividendClaimed event data must be 32 bytes, got {}", log.data.len()));
+    }
+
+    let epoch = U256::from_big_endian(log.topics[1].as_bytes());
+    if epoch > U256::from(i64::MAX as u64) {
+        return Err(anyhow::anyhow!("Epoch {} is out of range", epoch));
+    }
+    let amount = U256::from_big_endian(&log.data);
+    let amount_usdc = i128::try_from(amount)
+        .ok()
+        .and_then(|a| Decimal::try_from_i128_with_scale(a, 6).ok())
+        .ok_or_else(|| anyhow::anyhow!("Claimed amount {} is out of range", amount))?;
+
+    Ok(DividendClaimedEvent {
+        tx_hash: log
+            .transaction_hash
+            .map(|h| format!("{:?}", h))
+            .ok_or_else(|| anyhow::anyhow!("DividendClaimed log without transaction hash"))?,
+        log_index: log.log_index.map(|i| i.as_u64()).unwrap_or(0),
+        block_number: log
+            .block_number
+            .ok_or_else(|| anyhow::anyhow!("DividendClaimed log without block number"))?
+            .as_u64(),
+        epoch: epoch.as_u64(),
+        claimant: Address::from(log.topics[2]),
+        amount_usdc,
+    })
+}
+
+/// Record a chunk of `DividendClaimed` events and advance the checkpoint to `through_block`
+/// in one transaction. Events already recorded are skipped.
+async fn record_dividend_claims(
+    pool: &sqlx::PgPool,
+    vault_key: &str,
+    events: &[DividendClaimedEvent],
+    through_block: u64,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    for event in events {
+        let wallet_address = format!("{:#x}", event.claimant);
+
+        let claim: Option<(uuid::Uuid, Decimal)> = sqlx::query_as(
+            "SELECT c.id, c.amount_usdc FROM dividend_claims c
+             JOIN dividend_distributions d ON d.id = c.distribution_id
+             WHERE d.dividend_vault_address = $1 AND d.epoch = $2 AND LOWER(c.wallet_address) = $3",
+        )
+        .bind(vault_key)
+        .bind(event.epoch as i64)
+        .bind(&wallet_address)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let match_status = match &claim {
+            Some((_, amount)) if *amount == event.amount_usdc => "MATCHED",
+            Some(_) => "AMOUNT_MISMATCH",
+            None => "UNMATCHED",
+        };
+
+        let inserted = sqlx::query(
+            "INSERT INTO dividend_claim_events (tx_hash, log_index, dividend_vault_address, epoch, wallet_address,
+                                                amount_usdc, block_number, claim_id, match_status, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
+             ON CONFLICT (tx_hash, log_index) DO NOTHING",
+        )
+        .bind(&event.tx_hash)
+        .bind(event.log_index as i64)
+        .bind(vault_key)
+        .bind(event.epoch as i64)
+        .bind(&wallet_address)
+        .bind(event.amount_usdc)
+        .bind(event.block_number as i64)
+        .bind(claim.map(|(id, _)| id))
+        .bind(match_status)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if inserted == 0 {
+            continue;
+        }
+
+        // The vault paid out either way, so the claim can never be collected again
+        if let Some((claim_id, _)) = claim {
+            sqlx::query(
+                "UPDATE dividend_claims SET claimed = true, claim_tx_hash = $2, claimed_at = NOW()
+                 WHERE id = $1 AND claimed = false",
+            )
+            .bind(claim_id)
+            .bind(&event.tx_hash)
+            .execute(&mut *tx)
+            .await?;
+
+            claim_expiry::flag_claimed_after_expiry(&mut tx, claim_id, &event.tx_hash).await?;
+        }
+
+        if match_status == "MATCHED" {
+            continue;
+        }
+
+        let stored_amount = claim.map(|(_, amount)| amount.to_string());
+        tracing::error!(
+            "DividendClaimed {} for epoch {}: wallet={}, paid={} USDC, stored={:?}, tx={}",
+            match_status,
+            event.epoch,
+            wallet_address,
+            event.amount_usdc,
+            stored_amount,
+            event.tx_hash
+        );
+
+        let audit_payload = serde_json::json!({
+            "match_status": match_status,
+            "dividend_vault_address": vault_key,
+            "epoch": event.epoch,
+            "claim_id": claim.map(|(id, _)| id),
+            "paid_usdc": event.amount_usdc.to_string(),
+            "stored_usdc": stored_amount,
+            "tx_hash": event.tx_hash,
+        });
+
+        sqlx::query(
+            "INSERT INTO audit_logs (event_type, actor, target_resource, payload, created_at) VALUES ($1, $2, $3, $4, NOW())"
+        )
+        .bind("DIVIDEND_CLAIM_MISMATCH")
+        .bind(&wallet_address)
+        .bind(vault_key)
+        .bind(&audit_payload)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query(
+        "INSERT INTO dividend_vault_checkpoints (dividend_vault_address, last_indexed_block, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (dividend_vault_address) DO UPDATE
+         SET last_indexed_block = EXCLUDED.last_indexed_block, updated_at = NOW()",
+    )
+    .bind(vault_key)
+    .bind(through_block as i64)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    if !events.is_empty() {
+        tracing::info!("Indexed {} DividendClaimed events through block {}", events.len(), through_block);
+    }
+    Ok(())
+}
+
+/// Signature of the DividendVault event emitted by `redeemVoucher`.
+const VOUCHER_REDEEMED_SIGNATURE: &str = "VoucherRedeemed(uint256,address,uint256,uint256,address)";
+
+/// A decoded DividendVault `VoucherRedeemed` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoucherRedeemedEvent {
+    pub epoch: u64,
+    pub recipient: Address,
+    pub nonce: U256,
+    pub amount: U256,
+    pub signer: Address,
+}
+
+/// The on-chain state of a voucher redemption transaction.
+#[derive(Debug)]
+pub enum VoucherRedemption {
+    /// Not mined yet, or fewer than `CLAIM_CONFIRMATIONS` blocks deep.
+    Pending,
+    /// Mined and reverted.
+    Reverted,
+    /// Mined, succeeded and confirmed, with the `VoucherRedeemed` events the vault emitted.
+    Confirmed(Vec<VoucherRedeemedEvent>),
+}
+
+/// Look up voucher redemption `tx_hash` and decode the
+/// `VoucherRedeemed(uint256 indexed epoch, address indexed recipient, uint256 nonce, uint256 amount, address signer)`
+/// events emitted by `vault`. Logs from any other contract are ignored.
+pub async fn fetch_voucher_redemption(rpc_url: &str, tx_hash: H256, vault: Address) -> Result<VoucherRedemption> {
+    let provider = Provider::<Http>::try_from(rpc_url)?;
+
+    let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? else {
+        return Ok(VoucherRedemption::Pending);
+    };
+    let Some(mined) = receipt.block_number else {
+        return Ok(VoucherRedemption::Pending);
+    };
+    let head = provider.get_block_number().await?.as_u64();
+    match head.checked_sub(CLAIM_CONFIRMATIONS) {
+        Some(safe_head) if mined.as_u64() <= safe_head => {}
+        _ => return Ok(VoucherRedemption::Pending),
+    }
+
+    if receipt.status != Some(1.into()) {
+        return Ok(VoucherRedemption::Reverted);
+    }
+
+    let topic = H256::from(ethers::utils::keccak256(VOUCHER_REDEEMED_SIGNATURE));
+    let events = receipt
+        .logs
+        .iter()
+        .filter(|log| log.address == vault && log.topics.first() == Some(&topic))
+        .map(decode_voucher_redeemed_log)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(VoucherRedemption::Confirmed(events))
+}
+
+/// Decode a DividendVault `VoucherRedeemed` log.
+fn decode_voucher_redeemed_log(log: &Log) -> Result<VoucherRedeemedEvent> {
+    if log.topics.len() != 3 {
+        return Err(anyhow::anyhow!("VoucherRedeemed event must have 3 topics, got {}", log.topics.len()));
+    }
+    if log.data.len() != 96 {
+        return Err(anyhow::anyhow!("VoucherRedeemed event data must be 96 bytes, got {}", log.data.len()));
+    }
+
+    let epoch = U256::from_big_endian(log.topics[1].as_bytes());
+    if epoch > U256::from(i64::MAX as u64) {
+        return Err(anyhow::anyhow!("Epoch {} is out of range", epoch));
+    }
+
+    Ok(VoucherRedeemedEvent {
+        epoch: epoch.as_u64(),
+        recipient: Address::from(log.topics[2]),
+        nonce: U256::from_big_endian(&log.data[..32]),
+        amount: U256::from_big_endian(&log.data[32..64]),
+        signer: Address::from_slice(&log.data[76..96]),
+    })
+}
+
 /// Verify a specific transaction hash against expected parameters.
 #[allow(dead_code)]
 pub async fn verify_transaction(
@@ -203,3 +736,117 @@ pub async fn verify_transaction(
     // No matching Investment event found with expected amount
     Ok(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dividend_claimed_log(epoch: u64, claimant: Address, amount: u64) -> Log {
+        Log {
+            topics: vec![
+                H256::from(ethers::utils::keccak256("DividendClaimed(uint256,address,uint256)")),
+                H256::from_low_u64_be(epoch),
+                H256::from(claimant),
+            ],
+            data: H256::from_low_u64_be(amount).as_bytes().to_vec().into(),
+            transaction_hash: Some(H256::repeat_byte(0xaa)),
+            log_index: Some(U256::from(3)),
+            block_number: Some(U64::from(120)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decode_dividend_claimed_log() {
+        let claimant = Address::repeat_byte(0x42);
+        let event = decode_dividend_claimed_log(&dividend_claimed_log(7, claimant, 2_500_001)).unwrap();
+
+        assert_eq!(event.epoch, 7);
+        assert_eq!(event.claimant, claimant);
+        assert_eq!(event.amount_usdc, Decimal::from_str("2.500001").unwrap());
+        assert_eq!((event.log_index, event.block_number), (3, 120));
+        assert_eq!(event.tx_hash, format!("{:?}", H256::repeat_byte(0xaa)));
+    }
+
+    fn voucher_redeemed_log(epoch: u64, recipient: Address, nonce: u64, amount: u64, signer: Address) -> Log {
+        let mut data = Vec::with_capacity(96);
+        data.extend_from_slice(H256::from_low_u64_be(nonce).as_bytes());
+        data.extend_from_slice(H256::from_low_u64_be(amount).as_bytes());
+        data.extend_from_slice(H256::from(signer).as_bytes());
+        Log {
+            topics: vec![
+                H256::from(ethers::utils::keccak256(VOUCHER_REDEEMED_SIGNATURE)),
+                H256::from_low_u64_be(epoch),
+                H256::from(recipient),
+            ],
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decode_voucher_redeemed_log() {
+        let recipient = Address::repeat_byte(0x42);
+        let signer = Address::repeat_byte(0x51);
+        let event = decode_voucher_redeemed_log(&voucher_redeemed_log(4, recipient, 99, 2_500_001, signer)).unwrap();
+
+        assert_eq!(
+            event,
+            VoucherRedeemedEvent {
+                epoch: 4,
+                recipient,
+                nonce: U256::from(99),
+                amount: U256::from(2_500_001),
+                signer,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_voucher_redeemed_log() {
+        let mut log = voucher_redeemed_log(1, Address::zero(), 1, 1, Address::zero());
+        log.topics.pop();
+        assert!(decode_voucher_redeemed_log(&log).is_err());
+
+        let mut log = voucher_redeemed_log(1, Address::zero(), 1, 1, Address::zero());
+        log.data = log.data[..64].to_vec().into();
+        assert!(decode_voucher_redeemed_log(&log).is_err());
+    }
+
+    fn claimed_log(account: Address, amount: U256) -> Log {
+        let mut data = [0u8; 32];
+        amount.to_big_endian(&mut data);
+        Log {
+            topics: vec![H256::from(ethers::utils::keccak256("Claimed(address,uint256)")), H256::from(account)],
+            data: data.to_vec().into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decode_claimed_log() {
+        let account = Address::repeat_byte(0x42);
+        let (decoded, amount_usdc) = decode_claimed_log(&claimed_log(account, U256::from(2_500_001))).unwrap();
+
+        assert_eq!(decoded, account);
+        assert_eq!(amount_usdc, Decimal::from_str("2.500001").unwrap());
+    }
+
+    #[test]
+    fn test_decode_claimed_log_rejects_out_of_range_amount() {
+        let account = Address::repeat_byte(0x42);
+        assert!(decode_claimed_log(&claimed_log(account, U256::from(u128::MAX))).is_err());
+        assert!(decode_claimed_log(&claimed_log(account, U256::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_dividend_claimed_log() {
+        let mut log = dividend_claimed_log(1, Address::zero(), 1);
+        log.topics.pop();
+        assert!(decode_dividend_claimed_log(&log).is_err());
+
+        let mut log = dividend_claimed_log(1, Address::zero(), 1);
+        log.transaction_hash = None;
+        assert!(decode_dividend_claimed_log(&log).is_err());
+    }
+}
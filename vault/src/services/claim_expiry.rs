@@ -0,0 +1,188 @@
+//! Unclaimed Dividend Expiry
+//!
+//! Closes per-epoch distributions once their invention's claim window has passed since
+//! publication, and records every unclaimed claim in `unclaimed_dividends` for
+//! escheatment reporting.
+//!
+//! DividendVault has no expiry or sweep, so a holder can still claim on-chain after the
+//! window, and the USDC of an expired epoch never leaves the contract. Paying it again
+//! would fund it twice, so escheat is the only policy: expired amounts are never carried
+//! into a later distribution. Late claims are flagged on their ledger entry and audit-logged.
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::dividend::{DistributionStatus, UnclaimedDividend, UnclaimedPolicy};
+use crate::services::distribution_lifecycle::{transition_in, TransitionError};
+
+/// How often distributions are checked for an ended claim window.
+const CLAIM_EXPIRY_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Actor recorded on status changes and audit entries made by the expiry job.
+const EXPIRY_ACTOR: &str = "claim-expiry";
+
+/// Periodically expire distributions whose claim window has ended.
+pub async fn expire_unclaimed_dividends(pool: PgPool) -> Result<()> {
+    let mut interval = tokio::time::interval(CLAIM_EXPIRY_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = expire_due_distributions(&pool).await {
+            tracing::error!("Claim expiry run failed: {}", e);
+        }
+    }
+}
+
+/// Expire every published per-epoch distribution past its claim window.
+/// Returns how many were closed.
+pub async fn expire_due_distributions(pool: &PgPool) -> Result<usize> {
+    let due: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT d.id FROM dividend_distributions d
+         JOIN invention_ledger l ON l.invention_id = d.invention_id
+         WHERE d.status = 'PUBLISHED' AND d.mode = 'PER_EPOCH' AND l.claim_window_days IS NOT NULL
+           AND d.published_at + l.claim_window_days * INTERVAL '1 day' <= NOW()
+         ORDER BY d.published_at",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut expired = 0;
+    for distribution_id in due {
+        // One distribution per transaction; a failure is retried on the next run
+        match expire_distribution(pool, distribution_id).await {
+            Ok(true) => expired += 1,
+            Ok(false) => {}
+            Err(e) => tracing::error!("Failed to expire distribution {}: {}", distribution_id, e),
+        }
+    }
+    Ok(expired)
+}
+
+/// Close `distribution_id` and move its unclaimed claims into the ledger.
+/// Returns `false` if the distribution was no longer published.
+async fn expire_distribution(pool: &PgPool, distribution_id: Uuid) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    let (policy, window_days): (String, i32) = sqlx::query_as(
+        "SELECT l.unclaimed_policy, l.claim_window_days
+         FROM dividend_distributions d
+         JOIN invention_ledger l ON l.invention_id = d.invention_id
+         WHERE d.id = $1",
+    )
+    .bind(distribution_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    let policy: UnclaimedPolicy = policy.parse()?;
+
+    let reason = format!("claim window of {} days ended", window_days);
+    match transition_in(&mut tx, distribution_id, DistributionStatus::Closed, EXPIRY_ACTOR, Some(&reason)).await {
+        Ok(_) => {}
+        // Closed by hand since it was selected
+        Err(TransitionError::Invalid { .. }) | Err(TransitionError::NotFound) => return Ok(false),
+        Err(TransitionError::Db(e)) => return Err(e.into()),
+    }
+
+    let amounts: Vec<Decimal> = sqlx::query_scalar(
+        r#"
+        WITH expired AS (
+            UPDATE dividend_claims SET expired_at = NOW()
+            WHERE distribution_id = $1 AND claimed = false AND expired_at IS NULL
+            RETURNING id, wallet_address, amount_usdc
+        )
+        INSERT INTO unclaimed_dividends (claim_id, distribution_id, invention_id, wallet_address, amount_usdc,
+                                         policy, expired_at)
+        SELECT e.id, d.id, d.invention_id, LOWER(e.wallet_address), e.amount_usdc, $2, NOW()
+        FROM expired e, dividend_distributions d
+        WHERE d.id = $1
+        RETURNING amount_usdc
+        "#,
+    )
+    .bind(distribution_id)
+    .bind(policy.as_str())
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let total: Decimal = amounts.iter().sum();
+    let audit_payload = serde_json::json!({
+        "distribution_id": distribution_id,
+        "policy": policy.as_str(),
+        "claim_window_days": window_days,
+        "expired_claims": amounts.len(),
+        "expired_usdc": total.to_string(),
+    });
+
+    sqlx::query(
+        "INSERT INTO audit_logs (event_type, actor, target_resource, payload, created_at) VALUES ($1, $2, $3, $4, NOW())"
+    )
+    .bind("DIVIDEND_CLAIMS_EXPIRED")
+    .bind(EXPIRY_ACTOR)
+    .bind(distribution_id.to_string())
+    .bind(&audit_payload)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    tracing::info!(
+        "Distribution {} expired: {} unclaimed claims, {} USDC ({})",
+        distribution_id,
+        amounts.len(),
+        total,
+        policy.as_str()
+    );
+    Ok(true)
+}
+
+/// Flag an expired claim that was paid out on-chain anyway, and audit-log it.
+/// Does nothing for claims that had not expired.
+pub async fn flag_claimed_after_expiry(
+    conn: &mut sqlx::PgConnection,
+    claim_id: Uuid,
+    tx_hash: &str,
+) -> Result<()> {
+    let entry = sqlx::query_as::<_, UnclaimedDividend>(
+        "UPDATE unclaimed_dividends SET claimed_after_expiry_tx_hash = $2
+         WHERE claim_id = $1 AND claimed_after_expiry_tx_hash IS NULL
+         RETURNING *",
+    )
+    .bind(claim_id)
+    .bind(tx_hash)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let Some(entry) = entry else {
+        return Ok(());
+    };
+
+    tracing::error!(
+        "Expired claim {} ({} USDC, {}) was paid on-chain in {}",
+        claim_id,
+        entry.amount_usdc,
+        entry.policy,
+        tx_hash
+    );
+
+    let audit_payload = serde_json::json!({
+        "claim_id": claim_id,
+        "distribution_id": entry.distribution_id,
+        "amount_usdc": entry.amount_usdc.to_string(),
+        "policy": entry.policy,
+        "tx_hash": tx_hash,
+    });
+
+    sqlx::query(
+        "INSERT INTO audit_logs (event_type, actor, target_resource, payload, created_at) VALUES ($1, $2, $3, $4, NOW())"
+    )
+    .bind("EXPIRED_DIVIDEND_CLAIMED")
+    .bind(&entry.wallet_address)
+    .bind(entry.invention_id)
+    .bind(&audit_payload)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
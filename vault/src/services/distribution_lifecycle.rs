@@ -0,0 +1,201 @@
+//! Distribution Lifecycle
+//!
+//! Moves `dividend_distributions` through DRAFT → APPROVED → PUBLISHED → CLOSED,
+//! with VETOED and CANCELLED as the ways out before publication. Roots posted to the
+//! DividendVault pass through PUBLISHING while their transaction confirms, and go back
+//! to APPROVED only if it never funded the epoch. Each transition
+//! locks the distribution row before checking its current status, so two concurrent
+//! requests cannot both succeed, and is written to `audit_logs` in the same transaction.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::dividend::DistributionStatus;
+
+/// Why a transition was refused.
+#[derive(Debug)]
+pub enum TransitionError {
+    NotFound,
+    /// The distribution's current status does not allow the requested one.
+    Invalid {
+        from: DistributionStatus,
+        to: DistributionStatus,
+    },
+    Db(sqlx::Error),
+}
+
+impl std::fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "distribution not found"),
+            Self::Invalid { from, to } => write!(f, "cannot move from {} to {}", from.as_str(), to.as_str()),
+            Self::Db(e) => write!(f, "database error: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for TransitionError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Db(e)
+    }
+}
+
+impl From<TransitionError> for axum::http::StatusCode {
+    fn from(e: TransitionError) -> Self {
+        match e {
+            TransitionError::NotFound => Self::NOT_FOUND,
+            TransitionError::Invalid { .. } => Self::CONFLICT,
+            TransitionError::Db(_) => Self::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Whether a distribution in `from` may move to `to`.
+pub fn can_transition(from: DistributionStatus, to: DistributionStatus) -> bool {
+    use DistributionStatus::*;
+
+    matches!(
+        (from, to),
+        (Draft, Approved)
+            | (Draft, Vetoed)
+            | (Draft, Cancelled)
+            | (Approved, Publishing)
+            | (Approved, Published)
+            | (Approved, Vetoed)
+            | (Approved, Cancelled)
+            | (Publishing, Published)
+            | (Publishing, Approved)
+            | (Published, Closed)
+            | (Vetoed, Cancelled)
+    )
+}
+
+/// Move distribution `id` to `to` on behalf of `actor` and record a
+/// `DISTRIBUTION_STATUS_CHANGE` audit entry. Returns the previous status.
+pub async fn transition(
+    pool: &PgPool,
+    id: Uuid,
+    to: DistributionStatus,
+    actor: &str,
+    reason: Option<&str>,
+) -> Result<DistributionStatus, TransitionError> {
+    let mut tx = pool.begin().await?;
+    let from = transition_in(&mut tx, id, to, actor, reason).await?;
+    tx.commit().await?;
+
+    tracing::info!(
+        "Distribution {} moved from {} to {} by {}",
+        id,
+        from.as_str(),
+        to.as_str(),
+        actor
+    );
+    Ok(from)
+}
+
+/// [`transition`] inside the caller's transaction, for changes that must commit with it.
+pub async fn transition_in(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: Uuid,
+    to: DistributionStatus,
+    actor: &str,
+    reason: Option<&str>,
+) -> Result<DistributionStatus, TransitionError> {
+    let current: Option<(String, String)> = sqlx::query_as(
+        "SELECT status, invention_id FROM dividend_distributions WHERE id = $1 FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut **tx)
+    .await?;
+    let (from, invention_id) = current.ok_or(TransitionError::NotFound)?;
+
+    let from: DistributionStatus = from
+        .parse()
+        .map_err(|e: anyhow::Error| TransitionError::Db(sqlx::Error::Decode(e.into())))?;
+    if !can_transition(from, to) {
+        return Err(TransitionError::Invalid { from, to });
+    }
+
+    // Claim windows run from publication
+    sqlx::query(
+        "UPDATE dividend_distributions
+         SET status = $2, status_updated_at = NOW(),
+             published_at = CASE WHEN $2 = 'PUBLISHED' THEN NOW() ELSE published_at END
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(to.as_str())
+    .execute(&mut **tx)
+    .await?;
+
+    let audit_payload = serde_json::json!({
+        "distribution_id": id,
+        "invention_id": invention_id,
+        "from": from.as_str(),
+        "to": to.as_str(),
+        "reason": reason,
+    });
+
+    sqlx::query(
+        "INSERT INTO audit_logs (event_type, actor, target_resource, payload, created_at) VALUES ($1, $2, $3, $4, NOW())"
+    )
+    .bind("DISTRIBUTION_STATUS_CHANGE")
+    .bind(actor)
+    .bind(id.to_string())
+    .bind(&audit_payload)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DistributionStatus::*;
+
+    const ALL: [DistributionStatus; 7] = [Draft, Approved, Publishing, Published, Closed, Vetoed, Cancelled];
+
+    #[test]
+    fn test_happy_path() {
+        assert!(can_transition(Draft, Approved));
+        assert!(can_transition(Approved, Published));
+        assert!(can_transition(Published, Closed));
+    }
+
+    #[test]
+    fn test_publishing_claim() {
+        assert!(can_transition(Approved, Publishing));
+        assert!(can_transition(Publishing, Published));
+        // Released only when the epoch was never funded
+        assert!(can_transition(Publishing, Approved));
+        // Funds may already be on-chain
+        assert!(!can_transition(Publishing, Vetoed));
+        assert!(!can_transition(Publishing, Cancelled));
+    }
+
+    #[test]
+    fn test_veto_and_cancel_only_before_publication() {
+        for from in [Draft, Approved] {
+            assert!(can_transition(from, Vetoed));
+            assert!(can_transition(from, Cancelled));
+        }
+        for from in [Publishing, Published, Closed] {
+            assert!(!can_transition(from, Vetoed));
+            assert!(!can_transition(from, Cancelled));
+        }
+        assert!(can_transition(Vetoed, Cancelled));
+    }
+
+    #[test]
+    fn test_no_skipping_or_going_back() {
+        assert!(!can_transition(Draft, Published));
+        assert!(!can_transition(Approved, Draft));
+        assert!(!can_transition(Published, Approved));
+        for to in ALL {
+            assert!(!can_transition(Closed, to));
+            assert!(!can_transition(Cancelled, to));
+            assert!(!can_transition(to, to));
+        }
+    }
+}
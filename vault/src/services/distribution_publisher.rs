@@ -0,0 +1,681 @@
+//! Distribution Publisher
+//!
+//! Posts approved Merkle distributions to the DividendVault contract and funds the
+//! epochs of voucher distributions. The publisher claims the distribution by moving it
+//! to PUBLISHING, approves USDC for the epoch total, calls `createDistribution(root,
+//! totalAmount)` or `createVoucherDistribution(totalAmount)`, waits for confirmation
+//! and reads the assigned epoch from the `NewDistribution` event.
+//!
+//! The transaction is signed locally and stored with its nonce before it is broadcast,
+//! so a retry after a crash or timeout rebroadcasts that transaction instead of funding
+//! the epoch twice. The distribution only goes back to APPROVED once the transaction
+//! has reverted or its nonce was taken by another one.
+
+use anyhow::{anyhow, Result};
+use ethers::abi::Token;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::utils::id;
+use rust_decimal::prelude::*;
+use sqlx::PgPool;
+use std::str::FromStr;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::crypto::merkle::StandardLeafSchema;
+use crate::models::dividend::{DistributionStatus, PayoutMethod};
+use crate::services::distribution_lifecycle::{transition_in, TransitionError};
+
+/// Blocks a publication transaction must be buried under before its epoch is trusted.
+pub const PUBLISH_CONFIRMATIONS: usize = 2;
+
+/// How often the node is polled while waiting for a transaction.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Publisher wallet and contracts, from the environment.
+pub struct PublisherConfig {
+    pub dividend_vault: Address,
+    pub usdc: Address,
+    pub wallet: LocalWallet,
+}
+
+impl PublisherConfig {
+    /// Read `VAULT_PUBLISHER_PRIVATE_KEY`, `DIVIDEND_VAULT_ADDRESS`, `USDC_CONTRACT_ADDRESS` and `CHAIN_ID`.
+    ///
+    /// The key must own the DividendVault and hold the USDC being distributed.
+    /// Returns `None` when no publisher key is configured (local development).
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(key) = std::env::var("VAULT_PUBLISHER_PRIVATE_KEY") else {
+            return Ok(None);
+        };
+
+        let chain_id: u64 = std::env::var("CHAIN_ID")
+            .map_err(|_| anyhow!("CHAIN_ID must be set to publish distributions"))?
+            .parse()
+            .map_err(|e| anyhow!("Invalid CHAIN_ID: {}", e))?;
+        let wallet = LocalWallet::from_str(&key)
+            .map_err(|e| anyhow!("Invalid VAULT_PUBLISHER_PRIVATE_KEY: {}", e))?
+            .with_chain_id(chain_id);
+
+        let address_var = |name: &str| -> Result<Address> {
+            std::env::var(name)
+                .map_err(|_| anyhow!("{} must be set to publish distributions", name))?
+                .parse()
+                .map_err(|_| anyhow!("Invalid {}", name))
+        };
+
+        Ok(Some(Self {
+            dividend_vault: address_var("DIVIDEND_VAULT_ADDRESS")?,
+            usdc: address_var("USDC_CONTRACT_ADDRESS")?,
+            wallet,
+        }))
+    }
+}
+
+/// Where a distribution landed on-chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedEpoch {
+    pub epoch: u64,
+    pub tx_hash: H256,
+}
+
+/// Why a distribution could not be published.
+#[derive(Debug)]
+pub enum PublishError {
+    NotFound,
+    /// The distribution is not an approved per-epoch Merkle or voucher distribution DividendVault can pay.
+    NotPublishable(String),
+    Failed(anyhow::Error),
+}
+
+impl std::fmt::Display for PublishError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "distribution not found"),
+            Self::NotPublishable(reason) => write!(f, "not publishable: {}", reason),
+            Self::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for PublishError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Failed(e.into())
+    }
+}
+
+impl From<anyhow::Error> for PublishError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Failed(e)
+    }
+}
+
+impl From<TransitionError> for PublishError {
+    fn from(e: TransitionError) -> Self {
+        match e {
+            TransitionError::NotFound => Self::NotFound,
+            TransitionError::Invalid { .. } => Self::NotPublishable(e.to_string()),
+            TransitionError::Db(e) => Self::Failed(e.into()),
+        }
+    }
+}
+
+impl From<PublishError> for axum::http::StatusCode {
+    fn from(e: PublishError) -> Self {
+        match e {
+            PublishError::NotFound => Self::NOT_FOUND,
+            PublishError::NotPublishable(_) => Self::CONFLICT,
+            PublishError::Failed(_) => Self::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PublishableRow {
+    status: String,
+    payout_method: String,
+    mode: String,
+    merkle_root: Option<String>,
+    leaf_schema: sqlx::types::Json<StandardLeafSchema>,
+    publish_tx_hash: Option<String>,
+    publish_nonce: Option<i64>,
+    publish_raw_tx: Option<Vec<u8>>,
+    epoch: Option<i64>,
+}
+
+impl PublishableRow {
+    /// The stored `createDistribution` transaction, if one was signed.
+    fn signed(&self) -> Result<Option<SignedPublication>> {
+        let Some(tx_hash) = &self.publish_tx_hash else {
+            return Ok(None);
+        };
+        Ok(Some(SignedPublication {
+            tx_hash: parse_hash(tx_hash)?,
+            nonce: self.publish_nonce.map(|n| U256::from(n as u64)),
+            raw: self.publish_raw_tx.clone().map(Bytes::from),
+        }))
+    }
+}
+
+/// A `createDistribution` transaction, stored before it is broadcast.
+///
+/// Transactions sent before signing moved into the vault have only a hash.
+struct SignedPublication {
+    tx_hash: H256,
+    nonce: Option<U256>,
+    raw: Option<Bytes>,
+}
+
+/// Outcome of claiming a distribution for publication.
+enum Claim {
+    /// Already published; nothing left to send.
+    Published(PublishedEpoch),
+    /// Claimed as PUBLISHING, with the transaction an earlier attempt signed, if any.
+    /// `root` is `None` for a voucher epoch.
+    Pending {
+        root: Option<H256>,
+        total: U256,
+        signed: Option<SignedPublication>,
+    },
+}
+
+/// Publish approved distribution `distribution_id` to the DividendVault on behalf of
+/// `actor` and store the epoch and transaction hash. The distribution is moved to
+/// PUBLISHING first; moving it on to PUBLISHED is left for the caller.
+///
+/// A distribution that already has an epoch is returned as is. A PUBLISHING one is
+/// resumed: its stored transaction is rebroadcast and followed.
+pub async fn publish_distribution(
+    pool: &PgPool,
+    rpc_url: &str,
+    config: &PublisherConfig,
+    distribution_id: Uuid,
+    actor: &str,
+) -> Result<PublishedEpoch, PublishError> {
+    let provider = Provider::<Http>::try_from(rpc_url)
+        .map_err(|e| anyhow!("Invalid RPC URL: {}", e))?
+        .interval(POLL_INTERVAL);
+    let client = SignerMiddleware::new(provider, config.wallet.clone());
+
+    let (root, total, signed) = match claim_publication(pool, distribution_id, actor).await? {
+        Claim::Published(published) => return Ok(published),
+        Claim::Pending { root, total, signed } => (root, total, signed),
+    };
+
+    let signed = match signed {
+        Some(signed) => {
+            tracing::info!("Distribution {} already signed in {:?}; resuming", distribution_id, signed.tx_hash);
+            signed
+        }
+        None => {
+            // No row lock is held while the approve confirms
+            ensure_allowance(&client, config, total).await?;
+            sign_publication(pool, &client, config, distribution_id, root, total).await?
+        }
+    };
+    let tx_hash = signed.tx_hash;
+
+    let receipt = match broadcast(client.provider(), &signed).await {
+        Ok(()) => wait_for_receipt(client.provider(), tx_hash).await,
+        Err(e) => Err(e),
+    };
+    let receipt = match receipt {
+        Ok(receipt) => receipt,
+        Err(e) => {
+            if nonce_taken(client.provider(), client.address(), &signed).await? {
+                let reason = format!("createDistribution {:?} was replaced by another transaction", tx_hash);
+                release_publication(pool, distribution_id, tx_hash, actor, &reason).await?;
+            }
+            return Err(e.into());
+        }
+    };
+
+    if receipt.status != Some(U64::from(1)) {
+        // Reverted: nothing was funded, so the next attempt may sign a new transaction
+        let reason = format!("createDistribution reverted in {:?}", tx_hash);
+        release_publication(pool, distribution_id, tx_hash, actor, &reason).await?;
+        return Err(anyhow!(reason).into());
+    }
+
+    let (epoch, event_root, event_total) = parse_new_distribution(&receipt.logs, config.dividend_vault)?;
+    // Voucher epochs are announced with a zero root
+    let root = root.unwrap_or_default();
+    if event_root != root || event_total != total {
+        return Err(anyhow!(
+            "NewDistribution in {:?} is for root {:?} and {} base units, expected {:?} and {}",
+            tx_hash,
+            event_root,
+            event_total,
+            root,
+            total
+        )
+        .into());
+    }
+
+    sqlx::query("UPDATE dividend_distributions SET epoch = $2, dividend_vault_address = $3 WHERE id = $1")
+        .bind(distribution_id)
+        .bind(epoch as i64)
+        .bind(format!("{:#x}", config.dividend_vault))
+        .execute(pool)
+        .await?;
+
+    tracing::info!("Distribution {} published as epoch {} in {:?}", distribution_id, epoch, tx_hash);
+    Ok(PublishedEpoch { epoch, tx_hash })
+}
+
+async fn fetch_publishable(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    distribution_id: Uuid,
+) -> Result<PublishableRow, PublishError> {
+    sqlx::query_as::<_, PublishableRow>(
+        "SELECT status, payout_method, mode, merkle_root, leaf_schema, publish_tx_hash, publish_nonce,
+                publish_raw_tx, epoch
+         FROM dividend_distributions WHERE id = $1 FOR UPDATE",
+    )
+    .bind(distribution_id)
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or(PublishError::NotFound)
+}
+
+/// Move the distribution from APPROVED to PUBLISHING, or pick up one already PUBLISHING.
+async fn claim_publication(pool: &PgPool, distribution_id: Uuid, actor: &str) -> Result<Claim, PublishError> {
+    let mut tx = pool.begin().await?;
+    let row = fetch_publishable(&mut tx, distribution_id).await?;
+
+    if let (Some(epoch), Some(tx_hash)) = (row.epoch, &row.publish_tx_hash) {
+        return Ok(Claim::Published(PublishedEpoch {
+            epoch: epoch as u64,
+            tx_hash: parse_hash(tx_hash)?,
+        }));
+    }
+
+    let root = check_publishable(&row)?;
+    let total = fetch_total_base_units(&mut tx, distribution_id).await?;
+    if row.status == DistributionStatus::Approved.as_str() {
+        transition_in(&mut tx, distribution_id, DistributionStatus::Publishing, actor, None).await?;
+    }
+    let signed = row.signed()?;
+    tx.commit().await?;
+
+    Ok(Claim::Pending { root, total, signed })
+}
+
+/// Sign `createDistribution(root, total)`, or `createVoucherDistribution(total)` without a
+/// root, with the publisher's next nonce and store it, so the transaction exists in the
+/// database before any node has seen it.
+async fn sign_publication(
+    pool: &PgPool,
+    client: &SignerMiddleware<Provider<Http>, LocalWallet>,
+    config: &PublisherConfig,
+    distribution_id: Uuid,
+    root: Option<H256>,
+    total: U256,
+) -> Result<SignedPublication, PublishError> {
+    let mut tx = pool.begin().await?;
+    let row = fetch_publishable(&mut tx, distribution_id).await?;
+
+    // A concurrent attempt signed while this one was approving
+    if let Some(signed) = row.signed()? {
+        return Ok(signed);
+    }
+    if row.status != DistributionStatus::Publishing.as_str() {
+        return Err(PublishError::NotPublishable("distribution is no longer being published".to_string()));
+    }
+
+    let nonce = client
+        .get_transaction_count(client.address(), Some(BlockNumber::Pending.into()))
+        .await
+        .map_err(|e| anyhow!("Failed to fetch the publisher nonce: {}", e))?;
+    let mut request: TypedTransaction = TransactionRequest::new()
+        .to(config.dividend_vault)
+        .data(match root {
+            Some(root) => create_distribution_calldata(root, total),
+            None => create_voucher_distribution_calldata(total),
+        })
+        .nonce(nonce)
+        .into();
+    client
+        .fill_transaction(&mut request, None)
+        .await
+        .map_err(|e| anyhow!("createDistribution could not be prepared: {}", e))?;
+    let signature = config
+        .wallet
+        .sign_transaction(&request)
+        .await
+        .map_err(|e| anyhow!("createDistribution could not be signed: {}", e))?;
+    let raw = request.rlp_signed(&signature);
+    let tx_hash = H256::from(ethers::utils::keccak256(&raw));
+
+    sqlx::query(
+        "UPDATE dividend_distributions
+         SET publish_tx_hash = $2, publish_nonce = $3, publish_raw_tx = $4, dividend_vault_address = $5
+         WHERE id = $1",
+    )
+    .bind(distribution_id)
+    .bind(format!("{:?}", tx_hash))
+    .bind(nonce.as_u64() as i64)
+    .bind(raw.to_vec())
+    .bind(format!("{:#x}", config.dividend_vault))
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    tracing::info!("Distribution {} signed in {:?} with nonce {}", distribution_id, tx_hash, nonce);
+    Ok(SignedPublication {
+        tx_hash,
+        nonce: Some(nonce),
+        raw: Some(raw),
+    })
+}
+
+/// Send the stored transaction. A node that already has it, pending or mined, is fine.
+async fn broadcast(provider: &Provider<Http>, signed: &SignedPublication) -> Result<()> {
+    let Some(raw) = &signed.raw else {
+        return Ok(());
+    };
+    match provider.send_raw_transaction(raw.clone()).await {
+        Ok(_) => Ok(()),
+        Err(e) => match provider.get_transaction(signed.tx_hash).await {
+            Ok(Some(_)) => Ok(()),
+            _ => Err(anyhow!("createDistribution {:?} failed to broadcast: {}", signed.tx_hash, e)),
+        },
+    }
+}
+
+/// Whether `signed` can never be mined: a confirmed transaction already used its nonce.
+async fn nonce_taken(provider: &Provider<Http>, publisher: Address, signed: &SignedPublication) -> Result<bool> {
+    let Some(nonce) = signed.nonce else {
+        return Ok(false);
+    };
+    let head = provider
+        .get_block_number()
+        .await
+        .map_err(|e| anyhow!("Failed to fetch the block number: {}", e))?;
+    let Some(safe) = head.checked_sub(U64::from(PUBLISH_CONFIRMATIONS)) else {
+        return Ok(false);
+    };
+    let used = provider
+        .get_transaction_count(publisher, Some(BlockNumber::Number(safe).into()))
+        .await
+        .map_err(|e| anyhow!("Failed to fetch the publisher nonce: {}", e))?;
+    if used <= nonce {
+        return Ok(false);
+    }
+    // Looked up after the nonce, so a transaction mined in between is still found
+    let known = provider
+        .get_transaction(signed.tx_hash)
+        .await
+        .map_err(|e| anyhow!("Failed to look up {:?}: {}", signed.tx_hash, e))?;
+    Ok(known.is_none())
+}
+
+/// Forget the transaction `tx_hash` that never funded the epoch and move the
+/// distribution back to APPROVED, unless another attempt already did.
+async fn release_publication(
+    pool: &PgPool,
+    distribution_id: Uuid,
+    tx_hash: H256,
+    actor: &str,
+    reason: &str,
+) -> Result<(), PublishError> {
+    let mut tx = pool.begin().await?;
+    let cleared = sqlx::query(
+        "UPDATE dividend_distributions
+         SET publish_tx_hash = NULL, publish_nonce = NULL, publish_raw_tx = NULL
+         WHERE id = $1 AND publish_tx_hash = $2 AND status = 'PUBLISHING'",
+    )
+    .bind(distribution_id)
+    .bind(format!("{:?}", tx_hash))
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if cleared == 1 {
+        transition_in(&mut tx, distribution_id, DistributionStatus::Approved, actor, Some(reason)).await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// The root to publish, if DividendVault can pay the distribution; `None` for a voucher epoch.
+fn check_publishable(row: &PublishableRow) -> Result<Option<H256>, PublishError> {
+    let refuse = |reason: &str| Err(PublishError::NotPublishable(reason.to_string()));
+
+    if row.status != DistributionStatus::Approved.as_str() && row.status != DistributionStatus::Publishing.as_str() {
+        return refuse("only approved distributions can be published");
+    }
+    // Vouchers are always per-epoch and carry no tree
+    if row.payout_method == PayoutMethod::Voucher.as_str() {
+        return Ok(None);
+    }
+    if row.payout_method != PayoutMethod::Merkle.as_str() || row.mode != "PER_EPOCH" {
+        return refuse("only per-epoch Merkle and voucher distributions are published to DividendVault");
+    }
+    // DividendVault.claimDividend verifies abi.encode(msg.sender, amount) leaves
+    if row.leaf_schema.0 != StandardLeafSchema::AddressAmount {
+        return refuse("DividendVault only accepts address_amount leaves");
+    }
+    let Some(root) = &row.merkle_root else {
+        return refuse("distribution has no Merkle root");
+    };
+    Ok(Some(parse_hash(root)?))
+}
+
+/// Sum of the distribution's leaves in USDC base units, the amount the epoch must hold.
+async fn fetch_total_base_units(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    distribution_id: Uuid,
+) -> Result<U256, PublishError> {
+    let total: Option<Decimal> =
+        sqlx::query_scalar("SELECT SUM(amount_usdc) FROM dividend_claims WHERE distribution_id = $1")
+            .bind(distribution_id)
+            .fetch_one(&mut **tx)
+            .await?;
+
+    let base_units = total
+        .map(|t| (t * Decimal::from(1_000_000)).round())
+        .and_then(|t| t.to_u128())
+        .unwrap_or(0);
+    if base_units == 0 {
+        return Err(PublishError::NotPublishable("distribution pays nothing".to_string()));
+    }
+    Ok(U256::from(base_units))
+}
+
+/// Approve the DividendVault to pull `total` USDC from the publisher, unless it already may.
+async fn ensure_allowance(
+    client: &SignerMiddleware<Provider<Http>, LocalWallet>,
+    config: &PublisherConfig,
+    total: U256,
+) -> Result<()> {
+    let owner = client.address();
+    let call = TransactionRequest::new()
+        .to(config.usdc)
+        .data(allowance_calldata(owner, config.dividend_vault));
+    let result = client
+        .call(&call.into(), None)
+        .await
+        .map_err(|e| anyhow!("USDC allowance call failed: {}", e))?;
+    if result.len() != 32 {
+        return Err(anyhow!("USDC allowance returned {} bytes", result.len()));
+    }
+    if U256::from_big_endian(&result) >= total {
+        return Ok(());
+    }
+
+    let request = TransactionRequest::new()
+        .to(config.usdc)
+        .data(approve_calldata(config.dividend_vault, total));
+    let pending = client
+        .send_transaction(request, None)
+        .await
+        .map_err(|e| anyhow!("USDC approve failed to send: {}", e))?;
+    let tx_hash = pending.tx_hash();
+
+    let receipt = wait_for_receipt(client.provider(), tx_hash).await?;
+    if receipt.status != Some(U64::from(1)) {
+        return Err(anyhow!("USDC approve reverted in {:?}", tx_hash));
+    }
+    tracing::info!("Approved DividendVault for {} USDC base units in {:?}", total, tx_hash);
+    Ok(())
+}
+
+/// Wait until `tx_hash` is mined with [`PUBLISH_CONFIRMATIONS`] confirmations.
+async fn wait_for_receipt(provider: &Provider<Http>, tx_hash: H256) -> Result<TransactionReceipt> {
+    PendingTransaction::new(tx_hash, provider)
+        .confirmations(PUBLISH_CONFIRMATIONS)
+        .await
+        .map_err(|e| anyhow!("Failed waiting for {:?}: {}", tx_hash, e))?
+        .ok_or_else(|| anyhow!("Transaction {:?} was dropped", tx_hash))
+}
+
+/// Calldata for `DividendVault.createDistribution(bytes32 merkleRoot, uint256 totalAmount)`.
+pub fn create_distribution_calldata(root: H256, total: U256) -> Bytes {
+    encode_call(
+        "createDistribution(bytes32,uint256)",
+        &[Token::FixedBytes(root.as_bytes().to_vec()), Token::Uint(total)],
+    )
+}
+
+/// Calldata for `DividendVault.createVoucherDistribution(uint256 totalAmount)`.
+pub fn create_voucher_distribution_calldata(total: U256) -> Bytes {
+    encode_call("createVoucherDistribution(uint256)", &[Token::Uint(total)])
+}
+
+/// Calldata for `DividendVault.claimDividend(uint256 epoch, uint256 amount, bytes32[] merkleProof)`.
+pub fn claim_dividend_calldata(epoch: u64, amount: U256, proof: &[H256]) -> Bytes {
+    let proof = proof.iter().map(|node| Token::FixedBytes(node.as_bytes().to_vec())).collect();
+    encode_call(
+        "claimDividend(uint256,uint256,bytes32[])",
+        &[Token::Uint(U256::from(epoch)), Token::Uint(amount), Token::Array(proof)],
+    )
+}
+
+/// Calldata for `IERC20.approve(address spender, uint256 amount)`.
+pub fn approve_calldata(spender: Address, amount: U256) -> Bytes {
+    encode_call("approve(address,uint256)", &[Token::Address(spender), Token::Uint(amount)])
+}
+
+/// Calldata for `IERC20.allowance(address owner, address spender)`.
+pub fn allowance_calldata(owner: Address, spender: Address) -> Bytes {
+    encode_call("allowance(address,address)", &[Token::Address(owner), Token::Address(spender)])
+}
+
+fn encode_call(signature: &str, args: &[Token]) -> Bytes {
+    let mut calldata = id(signature).to_vec();
+    calldata.extend(ethers::abi::encode(args));
+    calldata.into()
+}
+
+/// Find `NewDistribution(uint256 indexed epoch, bytes32 merkleRoot, uint256 totalAmount)`
+/// emitted by `dividend_vault` and return `(epoch, merkleRoot, totalAmount)`.
+pub fn parse_new_distribution(logs: &[Log], dividend_vault: Address) -> Result<(u64, H256, U256)> {
+    let topic = H256::from(ethers::utils::keccak256("NewDistribution(uint256,bytes32,uint256)"));
+
+    let log = logs
+        .iter()
+        .find(|log| log.address == dividend_vault && log.topics.first() == Some(&topic))
+        .ok_or_else(|| anyhow!("No NewDistribution event from {:?}", dividend_vault))?;
+
+    if log.topics.len() != 2 || log.data.len() != 64 {
+        return Err(anyhow!("Malformed NewDistribution event"));
+    }
+    let epoch = U256::from_big_endian(log.topics[1].as_bytes());
+    if epoch > U256::from(i64::MAX as u64) {
+        return Err(anyhow!("Epoch {} is out of range", epoch));
+    }
+
+    Ok((
+        epoch.as_u64(),
+        H256::from_slice(&log.data[..32]),
+        U256::from_big_endian(&log.data[32..]),
+    ))
+}
+
+fn parse_hash(value: &str) -> Result<H256> {
+    value.parse().map_err(|_| anyhow!("Invalid 32-byte hash '{}'", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault() -> Address {
+        Address::repeat_byte(0xdd)
+    }
+
+    fn new_distribution_log(address: Address, epoch: u64, root: H256, total: u64) -> Log {
+        let mut data = root.as_bytes().to_vec();
+        data.extend(H256::from_low_u64_be(total).as_bytes());
+        Log {
+            address,
+            topics: vec![
+                H256::from(ethers::utils::keccak256("NewDistribution(uint256,bytes32,uint256)")),
+                H256::from_low_u64_be(epoch),
+            ],
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_create_distribution_calldata() {
+        let root = H256::repeat_byte(0xab);
+        let calldata = create_distribution_calldata(root, U256::from(6_000_000u64));
+
+        assert_eq!(calldata.len(), 4 + 64);
+        assert_eq!(&calldata[..4], &id("createDistribution(bytes32,uint256)"));
+        assert_eq!(&calldata[4..36], root.as_bytes());
+        assert_eq!(U256::from_big_endian(&calldata[36..]), U256::from(6_000_000u64));
+    }
+
+    #[test]
+    fn test_create_voucher_distribution_calldata() {
+        let calldata = create_voucher_distribution_calldata(U256::from(6_000_000u64));
+
+        assert_eq!(calldata.len(), 4 + 32);
+        assert_eq!(&calldata[..4], &id("createVoucherDistribution(uint256)"));
+        assert_eq!(U256::from_big_endian(&calldata[4..]), U256::from(6_000_000u64));
+    }
+
+    #[test]
+    fn test_claim_dividend_calldata() {
+        let proof = [H256::repeat_byte(0x01), H256::repeat_byte(0x02)];
+        let calldata = claim_dividend_calldata(3, U256::from(1_500_000u64), &proof);
+
+        // selector, epoch, amount, array offset, array length, two elements
+        assert_eq!(calldata.len(), 4 + 6 * 32);
+        assert_eq!(&calldata[..4], &id("claimDividend(uint256,uint256,bytes32[])"));
+        assert_eq!(U256::from_big_endian(&calldata[4..36]), U256::from(3));
+        assert_eq!(U256::from_big_endian(&calldata[36..68]), U256::from(1_500_000u64));
+        assert_eq!(U256::from_big_endian(&calldata[68..100]), U256::from(96));
+        assert_eq!(U256::from_big_endian(&calldata[100..132]), U256::from(2));
+        assert_eq!(&calldata[132..164], proof[0].as_bytes());
+        assert_eq!(&calldata[164..], proof[1].as_bytes());
+
+        // An empty proof is valid for a single-leaf tree
+        assert_eq!(claim_dividend_calldata(3, U256::one(), &[]).len(), 4 + 4 * 32);
+    }
+
+    #[test]
+    fn test_approve_selector() {
+        // Well-known ERC-20 selectors
+        assert_eq!(&approve_calldata(vault(), U256::one())[..4], &[0x09, 0x5e, 0xa7, 0xb3]);
+        assert_eq!(&allowance_calldata(vault(), vault())[..4], &[0xdd, 0x62, 0xed, 0x3e]);
+    }
+
+    #[test]
+    fn test_parse_new_distribution() {
+        let root = H256::repeat_byte(0x11);
+        let logs = vec![
+            // Same event from another contract is ignored
+            new_distribution_log(Address::repeat_byte(0x01), 99, root, 1),
+            new_distribution_log(vault(), 7, root, 6_000_000),
+        ];
+
+        let (epoch, event_root, total) = parse_new_distribution(&logs, vault()).unwrap();
+        assert_eq!((epoch, event_root, total), (7, root, U256::from(6_000_000u64)));
+
+        assert!(parse_new_distribution(&logs[..1], vault()).is_err());
+    }
+}
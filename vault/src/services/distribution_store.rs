@@ -0,0 +1,499 @@
+//! Distribution Persistence
+//!
+//! Stores a calculated or imported distribution in one transaction: its audit entry, the distribution row, its Merkle tree, every claim, its carry-forward
+//! balance changes and the idempotent response of the request that created it. Claims are inserted with a single
+//! `UNNEST` statement. Any failure rolls everything back, so a distribution is never
+//! left with part of its claims, or stored without its idempotent response.
+
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::crypto::standard_tree::StandardMerkleTree;
+use crate::models::dividend::{CarryForwardChange, DividendDistribution};
+use crate::services::idempotency::ClaimedKey;
+
+/// Why a distribution could not be stored.
+#[derive(Debug)]
+pub enum StoreError {
+    /// A carry-forward balance it pays out changed after it was calculated.
+    CarryForwardChanged { wallet_address: String },
+    Db(sqlx::Error),
+}
+
+impl std::fmt::Display for StoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CarryForwardChanged { wallet_address } => {
+                write!(f, "carry-forward balance of {} changed", wallet_address)
+            }
+            Self::Db(e) => write!(f, "database error: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for StoreError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Db(e)
+    }
+}
+
+impl From<StoreError> for axum::http::StatusCode {
+    fn from(e: StoreError) -> Self {
+        match e {
+            StoreError::CarryForwardChanged { .. } => Self::CONFLICT,
+            StoreError::Db(_) => Self::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// One claim of a new distribution.
+#[derive(Debug, Clone)]
+pub struct NewClaim {
+    pub id: Uuid,
+    pub wallet_address: String,
+    pub amount_usdc: Decimal,
+    /// Lifetime total committed to by the leaf (cumulative distributions only).
+    pub cumulative_amount_usdc: Option<Decimal>,
+    /// Leaf position in the tree (Merkle distributions only).
+    pub tree_index: Option<i32>,
+}
+
+/// A calculated distribution and everything stored with it.
+pub struct DistributionRecord<'a> {
+    pub distribution: &'a DividendDistribution,
+    pub tree: Option<&'a StandardMerkleTree>,
+    pub claims: Vec<NewClaim>,
+    /// Carry-forward balances credited or paid out by this distribution.
+    pub carry_forward: Vec<CarryForwardChange>,
+    /// Audit event type, such as `DIVIDEND_DISTRIBUTION`, and who caused it.
+    pub audit_event: &'a str,
+    pub actor: &'a str,
+    pub audit_payload: serde_json::Value,
+    /// Idempotency key of the request; the distribution is stored as its response.
+    pub idempotency_key: Option<&'a ClaimedKey>,
+}
+
+/// Store `record` atomically.
+pub async fn store_distribution(pool: &PgPool, record: &DistributionRecord<'_>) -> Result<(), StoreError> {
+    let mut tx = pool.begin().await?;
+    insert_distribution(&mut tx, record).await?;
+    if let Some(key) = record.idempotency_key {
+        key.complete(&mut tx, record.distribution).await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn insert_distribution(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    record: &DistributionRecord<'_>,
+) -> Result<(), StoreError> {
+    let distribution = record.distribution;
+
+    sqlx::query(
+        "INSERT INTO audit_logs (event_type, actor, target_resource, payload, created_at) VALUES ($1, $2, $3, $4, NOW())"
+    )
+    .bind(record.audit_event)
+    .bind(record.actor)
+    .bind(&distribution.invention_id)
+    .bind(&record.audit_payload)
+    .execute(&mut **tx)
+    .await?;
+
+    let dust_usdc = distribution
+        .dust
+        .as_ref()
+        .and_then(|d| d.amount_wei.parse::<Decimal>().ok())
+        .map(|wei| wei / Decimal::from(1_000_000))
+        .unwrap_or_default();
+    let attestation = distribution.attestation.as_ref();
+
+    sqlx::query(
+        r#"
+        INSERT INTO dividend_distributions (id, invention_id, total_revenue_usdc, merkle_root, claim_count, leaf_schema, mode, payout_method,
+                                            voucher_deadline, snapshot_block, dust_usdc, dust_recipients, revenue_breakdown,
+                                            attestation_signer, attestation_signature, attestation_chain_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, NOW())
+        "#,
+    )
+    .bind(distribution.id)
+    .bind(&distribution.invention_id)
+    .bind(distribution.total_revenue_usdc)
+    .bind(&distribution.merkle_root)
+    .bind(distribution.claim_count)
+    .bind(sqlx::types::Json(distribution.leaf_schema))
+    .bind(distribution.mode.as_str())
+    .bind(distribution.payout_method.as_str())
+    .bind(distribution.voucher_deadline)
+    .bind(distribution.snapshot_block)
+    .bind(dust_usdc)
+    .bind(sqlx::types::Json(distribution.dust.as_ref().map(|d| d.recipients.clone()).unwrap_or_default()))
+    .bind(sqlx::types::Json(&distribution.revenue_breakdown))
+    .bind(attestation.map(|a| a.signer.clone()))
+    .bind(attestation.map(|a| a.signature.clone()))
+    .bind(attestation.map(|a| a.message.chain_id as i64))
+    .execute(&mut **tx)
+    .await?;
+
+    // Proofs are derived from the stored tree on request
+    if let Some(tree) = record.tree {
+        insert_tree(&mut **tx, distribution.id, tree).await?;
+    }
+
+    insert_claims(tx, distribution.id, &record.claims).await?;
+
+    if !record.carry_forward.is_empty() {
+        update_carry_forward(tx, distribution, &record.carry_forward).await?;
+    }
+
+    Ok(())
+}
+
+/// Record `changes` against the invention's carry-forward balances, after checking
+/// that every balance paid out is still what the distribution was calculated with.
+async fn update_carry_forward(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    distribution: &DividendDistribution,
+    changes: &[CarryForwardChange],
+) -> Result<(), StoreError> {
+    // Distributions of the same invention update balances one at a time
+    sqlx::query("SELECT 1 FROM invention_ledger WHERE invention_id = $1 FOR UPDATE")
+        .bind(&distribution.invention_id)
+        .execute(&mut **tx)
+        .await?;
+
+    let wallets: Vec<&str> = changes.iter().map(|c| c.wallet_address.as_str()).collect();
+    let balances: Vec<(String, Decimal)> = sqlx::query_as(
+        "SELECT wallet_address, balance_usdc FROM dividend_carry_forward_balances
+         WHERE invention_id = $1 AND wallet_address = ANY($2)",
+    )
+    .bind(&distribution.invention_id)
+    .bind(&wallets)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    for change in changes.iter().filter(|c| c.amount_usdc.is_sign_negative()) {
+        let balance = balances
+            .iter()
+            .find(|(wallet, _)| *wallet == change.wallet_address)
+            .map(|(_, balance)| *balance)
+            .unwrap_or_default();
+        if balance != -change.amount_usdc {
+            return Err(StoreError::CarryForwardChanged {
+                wallet_address: change.wallet_address.clone(),
+            });
+        }
+    }
+
+    let amounts: Vec<Decimal> = changes.iter().map(|c| c.amount_usdc).collect();
+    sqlx::query(
+        r#"
+        INSERT INTO dividend_carry_forward (distribution_id, invention_id, wallet_address, amount_usdc, created_at)
+        SELECT $1, $2, c.wallet_address, c.amount_usdc, NOW()
+        FROM UNNEST($3::text[], $4::numeric[]) AS c(wallet_address, amount_usdc)
+        "#,
+    )
+    .bind(distribution.id)
+    .bind(&distribution.invention_id)
+    .bind(&wallets)
+    .bind(&amounts)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Insert every claim of `distribution_id` with one statement.
+async fn insert_claims(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    distribution_id: Uuid,
+    claims: &[NewClaim],
+) -> Result<(), sqlx::Error> {
+    let ids: Vec<Uuid> = claims.iter().map(|c| c.id).collect();
+    let wallets: Vec<&str> = claims.iter().map(|c| c.wallet_address.as_str()).collect();
+    let amounts: Vec<Decimal> = claims.iter().map(|c| c.amount_usdc).collect();
+    let cumulative: Vec<Option<Decimal>> = claims.iter().map(|c| c.cumulative_amount_usdc).collect();
+    let tree_indexes: Vec<Option<i32>> = claims.iter().map(|c| c.tree_index).collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO dividend_claims (id, distribution_id, wallet_address, amount_usdc, cumulative_amount_usdc, merkle_proof, tree_index, claimed, created_at)
+        SELECT c.id, $1, c.wallet_address, c.amount_usdc, c.cumulative_amount_usdc, '{}', c.tree_index, false, NOW()
+        FROM UNNEST($2::uuid[], $3::text[], $4::numeric[], $5::numeric[], $6::int4[])
+             AS c(id, wallet_address, amount_usdc, cumulative_amount_usdc, tree_index)
+        "#,
+    )
+    .bind(distribution_id)
+    .bind(&ids)
+    .bind(&wallets)
+    .bind(&amounts)
+    .bind(&cumulative)
+    .bind(&tree_indexes)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Store the tree of `distribution_id`.
+pub async fn insert_tree(
+    executor: impl sqlx::PgExecutor<'_>,
+    distribution_id: Uuid,
+    tree: &StandardMerkleTree,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO dividend_merkle_trees (distribution_id, leaf_count, nodes, created_at) VALUES ($1, $2, $3, NOW())",
+    )
+    .bind(distribution_id)
+    .bind(tree.len() as i32)
+    .bind(tree.to_bytes())
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::merkle::{ClaimLeaf, StandardLeafSchema};
+    use crate::models::dividend::{DistributionStatus, DistributionMode, PayoutMethod};
+
+    /// Pool for a scratch database with migrations applied, from `VAULT_TEST_DATABASE_URL`.
+    /// Tests that need one are `#[ignore]`d; run them with `cargo test -- --ignored`.
+    async fn test_pool() -> PgPool {
+        let url = std::env::var("VAULT_TEST_DATABASE_URL").expect("VAULT_TEST_DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.expect("connect to VAULT_TEST_DATABASE_URL");
+        sqlx::migrate!("./migrations").run(&pool).await.expect("run migrations");
+        pool
+    }
+
+    fn leaves() -> Vec<ClaimLeaf> {
+        ["0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc", "0x90f79bf6eb2c4f870365e785982e1f101e93b906"]
+            .iter()
+            .map(|addr| ClaimLeaf {
+                wallet_address: addr.to_string(),
+                amount_wei: "5000000".to_string(),
+            })
+            .collect()
+    }
+
+    fn distribution(invention_id: &str, tree: &StandardMerkleTree) -> DividendDistribution {
+        DividendDistribution {
+            id: Uuid::new_v4(),
+            invention_id: invention_id.to_string(),
+            total_revenue_usdc: Decimal::from(10),
+            merkle_root: Some(tree.root()),
+            claim_count: tree.len() as i32,
+            created_at: chrono::Utc::now(),
+            status: DistributionStatus::Draft,
+            epoch: None,
+            publish_tx_hash: None,
+            payout_method: PayoutMethod::Merkle,
+            voucher_deadline: None,
+            mode: DistributionMode::PerEpoch,
+            snapshot_block: None,
+            leaf_schema: StandardLeafSchema::default(),
+            merged_leaves: Vec::new(),
+            revenue_breakdown: None,
+            dust: None,
+            attestation: None,
+        }
+    }
+
+    fn claims(tree: &StandardMerkleTree) -> Vec<NewClaim> {
+        tree.claims()
+            .enumerate()
+            .map(|(i, leaf)| NewClaim {
+                id: Uuid::new_v4(),
+                wallet_address: leaf.wallet_address.clone(),
+                amount_usdc: Decimal::from(5),
+                cumulative_amount_usdc: None,
+                tree_index: tree.tree_index(i).map(|i| i as i32),
+            })
+            .collect()
+    }
+
+    /// Rows left behind for `distribution_id` / `invention_id`: audit entries,
+    /// distributions, trees and claims.
+    async fn stored_rows(pool: &PgPool, invention_id: &str, distribution_id: Uuid) -> (i64, i64, i64, i64) {
+        sqlx::query_as(
+            "SELECT (SELECT COUNT(*) FROM audit_logs WHERE event_type = 'DIVIDEND_DISTRIBUTION' AND target_resource = $1),
+                    (SELECT COUNT(*) FROM dividend_distributions WHERE id = $2),
+                    (SELECT COUNT(*) FROM dividend_merkle_trees WHERE distribution_id = $2),
+                    (SELECT COUNT(*) FROM dividend_claims WHERE distribution_id = $2)",
+        )
+        .bind(invention_id)
+        .bind(distribution_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs VAULT_TEST_DATABASE_URL"]
+    async fn test_stores_distribution_and_all_claims() {
+        let pool = test_pool().await;
+        let invention_id = format!("store-test-{}", Uuid::new_v4());
+        let tree = StandardMerkleTree::of(StandardLeafSchema::default(), &leaves()).unwrap();
+        let distribution = distribution(&invention_id, &tree);
+
+        let record = DistributionRecord {
+            distribution: &distribution,
+            tree: Some(&tree),
+            claims: claims(&tree),
+            carry_forward: Vec::new(),
+            audit_event: "DIVIDEND_DISTRIBUTION",
+            actor: "system",
+            audit_payload: serde_json::json!({}),
+            idempotency_key: None,
+        };
+        store_distribution(&pool, &record).await.unwrap();
+
+        assert_eq!(stored_rows(&pool, &invention_id, distribution.id).await, (1, 1, 1, 2));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs VAULT_TEST_DATABASE_URL"]
+    async fn test_failure_partway_leaves_nothing_behind() {
+        let pool = test_pool().await;
+        let invention_id = format!("store-test-{}", Uuid::new_v4());
+        let tree = StandardMerkleTree::of(StandardLeafSchema::default(), &leaves()).unwrap();
+        let distribution = distribution(&invention_id, &tree);
+
+        // The audit entry, distribution and tree are written before the claims insert
+        // fails on the repeated primary key
+        let mut claims = claims(&tree);
+        claims[1].id = claims[0].id;
+
+        let record = DistributionRecord {
+            distribution: &distribution,
+            tree: Some(&tree),
+            claims,
+            carry_forward: Vec::new(),
+            audit_event: "DIVIDEND_DISTRIBUTION",
+            actor: "system",
+            audit_payload: serde_json::json!({}),
+            idempotency_key: None,
+        };
+        assert!(matches!(store_distribution(&pool, &record).await, Err(StoreError::Db(_))));
+
+        assert_eq!(stored_rows(&pool, &invention_id, distribution.id).await, (0, 0, 0, 0));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs VAULT_TEST_DATABASE_URL"]
+    async fn test_idempotent_response_is_stored_with_the_distribution() {
+        let pool = test_pool().await;
+        let invention_id = format!("store-test-{}", Uuid::new_v4());
+        let tree = StandardMerkleTree::of(StandardLeafSchema::default(), &leaves()).unwrap();
+        let distribution = distribution(&invention_id, &tree);
+
+        let key = ClaimedKey::new("dividends.distribute", &invention_id);
+        sqlx::query("INSERT INTO idempotency_keys (scope, key, request_hash) VALUES ('dividends.distribute', $1, 'hash')")
+            .bind(&invention_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let response = |pool: PgPool| {
+            let invention_id = invention_id.clone();
+            async move {
+                sqlx::query_scalar::<_, Option<serde_json::Value>>(
+                    "SELECT response FROM idempotency_keys WHERE scope = 'dividends.distribute' AND key = $1",
+                )
+                .bind(invention_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+            }
+        };
+
+        let mut failing_claims = claims(&tree);
+        failing_claims[1].id = failing_claims[0].id;
+        let mut record = DistributionRecord {
+            distribution: &distribution,
+            tree: Some(&tree),
+            claims: failing_claims,
+            carry_forward: Vec::new(),
+            audit_event: "DIVIDEND_DISTRIBUTION",
+            actor: "system",
+            audit_payload: serde_json::json!({}),
+            idempotency_key: Some(&key),
+        };
+        assert!(store_distribution(&pool, &record).await.is_err());
+        assert_eq!(response(pool.clone()).await, None);
+
+        record.claims = claims(&tree);
+        store_distribution(&pool, &record).await.unwrap();
+        let stored = response(pool.clone()).await.unwrap();
+        assert_eq!(stored["id"], serde_json::json!(distribution.id));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs VAULT_TEST_DATABASE_URL"]
+    async fn test_carry_forward_balance_is_paid_out_once() {
+        let pool = test_pool().await;
+        let invention_id = format!("store-test-{}", Uuid::new_v4());
+        let tree = StandardMerkleTree::of(StandardLeafSchema::default(), &leaves()).unwrap();
+        let wallet = "0x15d34aaf54267db7d7c367839aaf71a00a2c6a65";
+
+        sqlx::query(
+            "INSERT INTO invention_ledger (invention_id, compliance_lawyer_id, platform_fee_percentage, inventor_share_percentage)
+             VALUES ($1, 'lawyer-1', 0, 0)",
+        )
+        .bind(&invention_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let change = |amount_usdc: Decimal| {
+            vec![CarryForwardChange {
+                wallet_address: wallet.to_string(),
+                amount_usdc,
+            }]
+        };
+        let store = |distribution: DividendDistribution, carry_forward: Vec<CarryForwardChange>| {
+            let pool = pool.clone();
+            let tree = &tree;
+            async move {
+                let record = DistributionRecord {
+                    distribution: &distribution,
+                    tree: Some(tree),
+                    claims: claims(tree),
+                        carry_forward,
+                    audit_event: "DIVIDEND_DISTRIBUTION",
+                    actor: "system",
+                    audit_payload: serde_json::json!({}),
+                    idempotency_key: None,
+                };
+                store_distribution(&pool, &record).await
+            }
+        };
+        let balance = || async {
+            sqlx::query_scalar::<_, Decimal>(
+                "SELECT balance_usdc FROM dividend_carry_forward_balances WHERE invention_id = $1 AND wallet_address = $2",
+            )
+            .bind(&invention_id)
+            .bind(wallet)
+            .fetch_optional(&pool)
+            .await
+            .unwrap()
+        };
+
+        // $0.004 credited, then paid out by the next distribution
+        store(distribution(&invention_id, &tree), change(Decimal::new(4_000, 6))).await.unwrap();
+        assert_eq!(balance().await, Some(Decimal::new(4_000, 6)));
+        store(distribution(&invention_id, &tree), change(Decimal::new(-4_000, 6))).await.unwrap();
+        assert_eq!(balance().await, None);
+
+        // A distribution calculated before that payout cannot pay it again
+        let stale = distribution(&invention_id, &tree);
+        let stale_id = stale.id;
+        assert!(matches!(
+            store(stale, change(Decimal::new(-4_000, 6))).await,
+            Err(StoreError::CarryForwardChanged { .. })
+        ));
+        assert_eq!(stored_rows(&pool, &invention_id, stale_id).await, (2, 0, 0, 0));
+    }
+}
@@ -0,0 +1,257 @@
+//! Compliance Fee Split Validation
+//!
+//! Rules a created or updated `compliance_fee_splits` row must satisfy, so
+//! `distribute_dividends` never deducts more than the revenue it received.
+//! Also resolves which fees apply to a distribution in ABS and standard mode.
+
+use anyhow::{anyhow, Result};
+use ethers::types::Address;
+use ethers::utils::to_checksum;
+use rust_decimal::Decimal;
+
+use crate::models::compliance::{FeeShare, FeeSplit, LedgerFeeConfig};
+
+/// Recipient types allowed by the `compliance_fee_splits.recipient_type` CHECK constraint.
+pub const RECIPIENT_TYPES: [&str; 4] = ["LAWYER", "PLATFORM", "INVENTOR", "DAO_TREASURY"];
+
+/// Validate a recipient address: well-formed and in EIP-55 checksum form.
+pub fn validate_recipient_address(address: &str) -> Result<()> {
+    let parsed: Address = address
+        .parse()
+        .map_err(|_| anyhow!("Invalid recipient address '{}'", address))?;
+
+    let checksummed = to_checksum(&parsed, None);
+    if checksummed != address {
+        return Err(anyhow!(
+            "Recipient address '{}' is not EIP-55 checksummed (expected '{}')",
+            address,
+            checksummed
+        ));
+    }
+    Ok(())
+}
+
+/// Validate a single split's type, address and percentage.
+pub fn validate_fee_split(recipient_type: &str, recipient_address: &str, percentage: Decimal) -> Result<()> {
+    if !RECIPIENT_TYPES.contains(&recipient_type) {
+        return Err(anyhow!("Unknown recipient type '{}'", recipient_type));
+    }
+    validate_recipient_address(recipient_address)?;
+
+    // NUMERIC(5, 2): positive, at most 100, two decimal places
+    if percentage <= Decimal::ZERO || percentage > Decimal::from(100) {
+        return Err(anyhow!("Percentage must be in (0, 100], got {}", percentage));
+    }
+    if percentage.normalize().scale() > 2 {
+        return Err(anyhow!("Percentage has more than 2 decimal places: {}", percentage));
+    }
+    Ok(())
+}
+
+/// Validate a created or updated split against the split set as it would be afterwards.
+///
+/// Only `changed` is checked in full, so an older row that breaks a rule never blocks
+/// an unrelated change. The total may not exceed 100%, and `changed` may share its
+/// recipient type with another split only if one of the two allows duplicates.
+pub fn validate_fee_split_change(splits: &[FeeSplit], changed: &FeeSplit) -> Result<()> {
+    validate_fee_split(&changed.recipient_type, &changed.recipient_address, changed.percentage)?;
+
+    let total: Decimal = splits.iter().map(|s| s.percentage).sum();
+    if total > Decimal::from(100) {
+        return Err(anyhow!("Fee splits total {}%, more than 100%", total));
+    }
+
+    let duplicate = splits.iter().any(|s| {
+        s.id != changed.id
+            && s.recipient_type == changed.recipient_type
+            && !(s.allow_duplicate_type || changed.allow_duplicate_type)
+    });
+    if duplicate {
+        return Err(anyhow!("More than one {} fee split", changed.recipient_type));
+    }
+    Ok(())
+}
+
+/// Fees for an ABS-mode invention: its `compliance_fee_splits` rows.
+///
+/// ABS mode requires an assigned compliance lawyer and at least one split.
+pub fn abs_fee_shares(config: &LedgerFeeConfig, splits: Vec<FeeShare>) -> Result<Vec<FeeShare>> {
+    if config.compliance_lawyer_id.is_none() {
+        return Err(anyhow!("ABS fee sharing is enabled but no compliance lawyer is assigned"));
+    }
+    if splits.is_empty() {
+        return Err(anyhow!("ABS fee sharing is enabled but no fee splits are configured"));
+    }
+    Ok(splits)
+}
+
+/// Fees for a standard-mode invention: platform fee and inventor share from the ledger.
+///
+/// Both percentages must be configured, with a recipient for each non-zero one.
+pub fn standard_fee_shares(config: &LedgerFeeConfig) -> Result<Vec<FeeShare>> {
+    let configured = [
+        ("PLATFORM", config.platform_fee_percentage, &config.platform_fee_address),
+        ("INVENTOR", config.inventor_share_percentage, &config.inventor_wallet_address),
+    ];
+
+    let mut shares = Vec::new();
+    for (recipient_type, percentage, address) in configured {
+        let percentage = percentage
+            .ok_or_else(|| anyhow!("No {} percentage configured for standard fee mode", recipient_type))?;
+        if percentage < Decimal::ZERO || percentage > Decimal::from(100) {
+            return Err(anyhow!("{} percentage must be in [0, 100], got {}", recipient_type, percentage));
+        }
+        if percentage.is_zero() {
+            continue;
+        }
+
+        let address = address
+            .as_ref()
+            .ok_or_else(|| anyhow!("No {} address configured for standard fee mode", recipient_type))?;
+        address
+            .parse::<Address>()
+            .map_err(|_| anyhow!("Invalid {} address '{}'", recipient_type, address))?;
+
+        shares.push(FeeShare {
+            recipient_type: recipient_type.to_string(),
+            recipient_address: address.clone(),
+            percentage,
+        });
+    }
+
+    let total: Decimal = shares.iter().map(|s| s.percentage).sum();
+    if total > Decimal::from(100) {
+        return Err(anyhow!("Platform fee and inventor share total {}%, more than 100%", total));
+    }
+    Ok(shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    const LAWYER: &str = "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC";
+    const TREASURY: &str = "0x90F79bf6EB2c4f870365E785982E1f101E93b906";
+
+    fn split(recipient_type: &str, recipient_address: &str, percentage: &str) -> FeeSplit {
+        FeeSplit {
+            id: Uuid::new_v4(),
+            invention_id: "inv-1".to_string(),
+            recipient_type: recipient_type.to_string(),
+            recipient_address: recipient_address.to_string(),
+            percentage: Decimal::from_str(percentage).unwrap(),
+            allow_duplicate_type: false,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_valid_split_change() {
+        let splits = [split("LAWYER", LAWYER, "20"), split("PLATFORM", TREASURY, "5.5")];
+        assert!(validate_fee_split_change(&splits, &splits[1]).is_ok());
+    }
+
+    #[test]
+    fn test_total_over_100_percent_is_rejected() {
+        let splits = [split("LAWYER", LAWYER, "60"), split("PLATFORM", TREASURY, "40.01")];
+        assert!(validate_fee_split_change(&splits, &splits[1]).is_err());
+
+        let exactly_100 = [split("LAWYER", LAWYER, "60"), split("PLATFORM", TREASURY, "40")];
+        assert!(validate_fee_split_change(&exactly_100, &exactly_100[1]).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_recipient_type_needs_opt_in_on_either_split() {
+        let mut splits = [split("LAWYER", LAWYER, "10"), split("LAWYER", TREASURY, "10")];
+        assert!(validate_fee_split_change(&splits, &splits[1]).is_err());
+
+        // The allowance stays on the row, so later edits of either split pass
+        splits[1].allow_duplicate_type = true;
+        assert!(validate_fee_split_change(&splits, &splits[1]).is_ok());
+        assert!(validate_fee_split_change(&splits, &splits[0]).is_ok());
+    }
+
+    #[test]
+    fn test_only_the_changed_split_is_checked() {
+        // An older row with a lowercase address does not block changes to other rows
+        let splits = [split("LAWYER", &LAWYER.to_lowercase(), "10"), split("PLATFORM", TREASURY, "10")];
+        assert!(validate_fee_split_change(&splits, &splits[1]).is_ok());
+        assert!(validate_fee_split_change(&splits, &splits[0]).is_err());
+    }
+
+    #[test]
+    fn test_address_must_be_checksummed() {
+        assert!(validate_recipient_address(LAWYER).is_ok());
+        assert!(validate_recipient_address(&LAWYER.to_lowercase()).is_err());
+        assert!(validate_recipient_address("0x3C44CdDdB6a900fa2b585dd299e03d12FA4293Bc").is_err());
+        assert!(validate_recipient_address("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_invalid_percentage_and_type() {
+        assert!(validate_fee_split("LAWYER", LAWYER, Decimal::ZERO).is_err());
+        assert!(validate_fee_split("LAWYER", LAWYER, Decimal::from_str("10.005").unwrap()).is_err());
+        assert!(validate_fee_split("ADVISOR", LAWYER, Decimal::from(10)).is_err());
+    }
+
+    fn ledger(fee_sharing_enabled: bool) -> LedgerFeeConfig {
+        LedgerFeeConfig {
+            fee_sharing_enabled,
+            compliance_lawyer_id: None,
+            platform_fee_percentage: Some(Decimal::from_str("2.5").unwrap()),
+            platform_fee_address: Some(TREASURY.to_string()),
+            inventor_share_percentage: Some(Decimal::from(10)),
+            inventor_wallet_address: Some(LAWYER.to_lowercase()),
+        }
+    }
+
+    #[test]
+    fn test_standard_shares_from_ledger() {
+        let shares = standard_fee_shares(&ledger(false)).unwrap();
+        let summary: Vec<(&str, Decimal)> = shares
+            .iter()
+            .map(|s| (s.recipient_type.as_str(), s.percentage))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("PLATFORM", Decimal::from_str("2.5").unwrap()), ("INVENTOR", Decimal::from(10))]
+        );
+    }
+
+    #[test]
+    fn test_standard_mode_requires_configuration() {
+        let mut missing_fee = ledger(false);
+        missing_fee.platform_fee_percentage = None;
+        assert!(standard_fee_shares(&missing_fee).is_err());
+
+        let mut missing_wallet = ledger(false);
+        missing_wallet.inventor_wallet_address = None;
+        assert!(standard_fee_shares(&missing_wallet).is_err());
+
+        // A zero inventor share needs no wallet
+        missing_wallet.inventor_share_percentage = Some(Decimal::ZERO);
+        assert_eq!(standard_fee_shares(&missing_wallet).unwrap().len(), 1);
+
+        let mut over = ledger(false);
+        over.inventor_share_percentage = Some(Decimal::from(98));
+        assert!(standard_fee_shares(&over).is_err());
+    }
+
+    #[test]
+    fn test_abs_mode_requires_lawyer_and_splits() {
+        let share = FeeShare {
+            recipient_type: "LAWYER".to_string(),
+            recipient_address: LAWYER.to_string(),
+            percentage: Decimal::from(20),
+        };
+
+        let mut config = ledger(true);
+        assert!(abs_fee_shares(&config, vec![share.clone()]).is_err());
+
+        config.compliance_lawyer_id = Some("lawyer-1".to_string());
+        assert!(abs_fee_shares(&config, Vec::new()).is_err());
+        assert_eq!(abs_fee_shares(&config, vec![share]).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,149 @@
+//! RoyaltyToken Holder Snapshots
+//!
+//! Reads every holder's RoyaltyToken balance at a given block from the Transfer index,
+//! so dividend shares come from chain data rather than from whoever calls the Vault.
+//! The index is built in the background by `token_indexer` from plain `eth_getLogs`
+//! calls, so any full node works (no archive state). Also holds the `Transfer`
+//! decoding and replay helpers the indexer uses.
+
+use anyhow::{anyhow, Result};
+use ethers::types::{Address, Log, U256};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::services::token_indexer;
+
+/// RoyaltyToken uses 18 decimals.
+const ROYALTY_TOKEN_DECIMALS: u32 = 18;
+
+/// Snapshot `token` holder balances as of the end of `snapshot_block` from the Transfer
+/// index, as `(lowercase address, balance in whole tokens)` sorted by address. Empty
+/// wallets are omitted.
+///
+/// Returns `None` if the index has not reached the block yet.
+pub async fn snapshot_holders_from_index(
+    pool: &sqlx::PgPool,
+    token: Address,
+    snapshot_block: u64,
+) -> Result<Option<Vec<(String, Decimal)>>> {
+    let balances = token_indexer::balances_at_block(pool, &format!("{:#x}", token), snapshot_block).await?;
+    balances
+        .map(|b| to_token_balances(b.into_iter().collect()))
+        .transpose()
+}
+
+/// Decode an ERC-20 `Transfer(address indexed from, address indexed to, uint256 value)` log.
+pub fn decode_transfer_log(log: &Log) -> Result<(Address, Address, U256)> {
+    if log.topics.len() != 3 {
+        return Err(anyhow!("Transfer event must have 3 topics, got {}", log.topics.len()));
+    }
+    if log.data.len() != 32 {
+        return Err(anyhow!("Transfer event data must be 32 bytes, got {}", log.data.len()));
+    }
+
+    Ok((
+        Address::from(log.topics[1]),
+        Address::from(log.topics[2]),
+        U256::from_big_endian(&log.data),
+    ))
+}
+
+/// Move `value` from `sender` to `recipient`. The zero address mints and burns.
+pub fn apply_transfer(
+    balances: &mut HashMap<Address, U256>,
+    sender: Address,
+    recipient: Address,
+    value: U256,
+) -> Result<()> {
+    if !sender.is_zero() {
+        let balance = balances.entry(sender).or_default();
+        // A negative balance means logs are missing; never guess
+        *balance = balance
+            .checked_sub(value)
+            .ok_or_else(|| anyhow!("Transfer of {} exceeds balance of {:?}", value, sender))?;
+    }
+    if !recipient.is_zero() {
+        let balance = balances.entry(recipient).or_default();
+        *balance = balance
+            .checked_add(value)
+            .ok_or_else(|| anyhow!("Balance overflow for {:?}", recipient))?;
+    }
+    Ok(())
+}
+
+/// Convert base-unit balances to whole tokens, dropping empty wallets.
+pub(crate) fn to_token_balances(balances: HashMap<Address, U256>) -> Result<Vec<(String, Decimal)>> {
+    let mut holders = balances
+        .into_iter()
+        .filter(|(_, balance)| !balance.is_zero())
+        .map(|(address, balance)| {
+            let too_large = || anyhow!("Balance of {:?} is too large: {}", address, balance);
+            let base_units = i128::try_from(balance).map_err(|_| too_large())?;
+            let amount = Decimal::try_from_i128_with_scale(base_units, ROYALTY_TOKEN_DECIMALS)
+                .map_err(|_| too_large())?;
+            Ok((format!("{:#x}", address), amount))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    holders.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(holders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Bytes, H256};
+    use std::str::FromStr;
+
+    fn addr(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn tokens(n: u64) -> U256 {
+        U256::from(n) * U256::exp10(18)
+    }
+
+    #[test]
+    fn test_replay_mints_transfers_and_burns() {
+        let mut balances = HashMap::new();
+        apply_transfer(&mut balances, Address::zero(), addr(1), tokens(100)).unwrap();
+        apply_transfer(&mut balances, addr(1), addr(2), tokens(30)).unwrap();
+        apply_transfer(&mut balances, addr(2), Address::zero(), tokens(30)).unwrap();
+
+        let holders = to_token_balances(balances).unwrap();
+        assert_eq!(holders, vec![(format!("{:#x}", addr(1)), Decimal::from(70))]);
+    }
+
+    #[test]
+    fn test_overdrawn_sender_is_an_error() {
+        let mut balances = HashMap::new();
+        apply_transfer(&mut balances, Address::zero(), addr(1), tokens(1)).unwrap();
+        assert!(apply_transfer(&mut balances, addr(1), addr(2), tokens(2)).is_err());
+    }
+
+    #[test]
+    fn test_fractional_balances_keep_full_precision() {
+        let mut balances = HashMap::new();
+        apply_transfer(&mut balances, Address::zero(), addr(1), U256::from(1_500_000_000_000_000_001u128)).unwrap();
+
+        let holders = to_token_balances(balances).unwrap();
+        assert_eq!(holders[0].1, Decimal::from_str("1.500000000000000001").unwrap());
+    }
+
+    #[test]
+    fn test_decode_transfer_log() {
+        let log = Log {
+            topics: vec![
+                H256::zero(),
+                H256::from(addr(1)),
+                H256::from(addr(2)),
+            ],
+            data: Bytes::from(H256::from_low_u64_be(42).as_bytes().to_vec()),
+            ..Default::default()
+        };
+        assert_eq!(decode_transfer_log(&log).unwrap(), (addr(1), addr(2), U256::from(42)));
+
+        let missing_topic = Log { topics: vec![H256::zero()], ..log };
+        assert!(decode_transfer_log(&missing_topic).is_err());
+    }
+}
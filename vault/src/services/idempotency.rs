@@ -0,0 +1,255 @@
+//! Idempotency Keys
+//!
+//! Lets clients retry `distribute` and `verify` safely. The first request with an
+//! `Idempotency-Key` header claims the key and stores a SHA-256 fingerprint of the
+//! request; when it succeeds, its response is stored and replayed to every retry with
+//! the same key. Reusing a key for a different request is rejected.
+//!
+//! Handlers that write in a transaction store the response through [`ClaimedKey`] in
+//! that same transaction, so a request that took effect always has its response.
+//!
+//! Failed requests release their key, so a retry runs the request again. A key whose
+//! request never finished (e.g. the Vault restarted) can be reclaimed by the same
+//! request after [`IDEMPOTENCY_LOCK_TIMEOUT_SECS`].
+
+use axum::http::{HeaderMap, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::future::Future;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Longest key accepted, in bytes.
+const MAX_KEY_LEN: usize = 255;
+
+/// How long a key stays locked by a request that has not finished.
+pub const IDEMPOTENCY_LOCK_TIMEOUT_SECS: i64 = 10 * 60;
+
+/// The request's idempotency key, if it sent one. Empty or oversized keys are a 400.
+pub fn idempotency_key_from_headers(headers: &HeaderMap) -> Result<Option<String>, StatusCode> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+
+    let key = value.to_str().map(str::trim).unwrap_or_default();
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        tracing::warn!("Invalid {} header", IDEMPOTENCY_KEY_HEADER);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(Some(key.to_string()))
+}
+
+/// SHA-256 of `request` as JSON, bound to `scope`, as lowercase hex.
+pub fn fingerprint(scope: &str, request: &impl Serialize) -> Result<String, serde_json::Error> {
+    let mut hasher = Sha256::new();
+    hasher.update(scope.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(serde_json::to_vec(request)?);
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// An idempotency key claimed by the running request.
+#[derive(Debug, Clone)]
+pub struct ClaimedKey {
+    scope: String,
+    key: String,
+}
+
+impl ClaimedKey {
+    pub(crate) fn new(scope: &str, key: &str) -> Self {
+        Self {
+            scope: scope.to_string(),
+            key: key.to_string(),
+        }
+    }
+
+    /// Store `response` as the key's outcome on `conn`. Called inside the handler's
+    /// transaction, the response commits or rolls back together with the request.
+    pub async fn complete(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        response: &(impl Serialize + Sync),
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE idempotency_keys SET response = $3, completed_at = NOW() WHERE scope = $1 AND key = $2",
+        )
+        .bind(&self.scope)
+        .bind(&self.key)
+        .bind(sqlx::types::Json(response))
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Run `handler` at most once per `Idempotency-Key` within `scope`.
+///
+/// Without a key the handler just runs. With one, a stored response for the same
+/// request is returned instead of running it; a different request with the key is a
+/// 422, and a request still running with the key is a 409. The handler receives the
+/// claimed key to store its response transactionally; otherwise it is stored after
+/// the handler returns.
+pub async fn run_idempotent<T, F, H>(
+    pool: &PgPool,
+    headers: &HeaderMap,
+    scope: &str,
+    request: &impl Serialize,
+    handler: H,
+) -> Result<T, StatusCode>
+where
+    T: Serialize + DeserializeOwned,
+    H: FnOnce(Option<ClaimedKey>) -> F,
+    F: Future<Output = Result<T, StatusCode>>,
+{
+    let Some(key) = idempotency_key_from_headers(headers)? else {
+        return handler(None).await;
+    };
+
+    let request_hash = fingerprint(scope, request).map_err(|e| {
+        tracing::error!("Failed to fingerprint {} request: {}", scope, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if let Some(stored) = claim_key(pool, scope, &key, &request_hash).await.map_err(|e| {
+        tracing::error!("Failed to claim idempotency key for {}: {}", scope, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })? {
+        return stored;
+    }
+
+    match handler(Some(ClaimedKey::new(scope, &key))).await {
+        Ok(response) => {
+            let body = serde_json::to_value(&response).map_err(|e| {
+                tracing::error!("Failed to serialize {} response: {}", scope, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            // Already stored if the handler completed the key; otherwise the request has
+            // taken effect and a failure here only costs the replay
+            if let Err(e) = store_response(pool, scope, &key, &body).await {
+                tracing::error!("Failed to store response for idempotency key {} ({}): {}", key, scope, e);
+            }
+            Ok(response)
+        }
+        Err(status) => {
+            if let Err(e) = release_key(pool, scope, &key).await {
+                tracing::error!("Failed to release idempotency key {} ({}): {}", key, scope, e);
+            }
+            Err(status)
+        }
+    }
+}
+
+/// Claim `key` for this request. Returns `None` if the caller should run the request,
+/// or the outcome to return instead.
+async fn claim_key<T: DeserializeOwned>(
+    pool: &PgPool,
+    scope: &str,
+    key: &str,
+    request_hash: &str,
+) -> Result<Option<Result<T, StatusCode>>, sqlx::Error> {
+    // New keys are inserted; abandoned ones are taken over by the same request
+    let claimed: Option<String> = sqlx::query_scalar(
+        "INSERT INTO idempotency_keys (scope, key, request_hash) VALUES ($1, $2, $3)
+         ON CONFLICT (scope, key) DO UPDATE SET created_at = NOW()
+         WHERE idempotency_keys.response IS NULL
+           AND idempotency_keys.request_hash = EXCLUDED.request_hash
+           AND idempotency_keys.created_at < NOW() - $4 * INTERVAL '1 second'
+         RETURNING key",
+    )
+    .bind(scope)
+    .bind(key)
+    .bind(request_hash)
+    .bind(IDEMPOTENCY_LOCK_TIMEOUT_SECS as f64)
+    .fetch_optional(pool)
+    .await?;
+    if claimed.is_some() {
+        return Ok(None);
+    }
+
+    let existing: Option<(String, Option<serde_json::Value>)> = sqlx::query_as(
+        "SELECT request_hash, response FROM idempotency_keys WHERE scope = $1 AND key = $2",
+    )
+    .bind(scope)
+    .bind(key)
+    .fetch_optional(pool)
+    .await?;
+
+    let outcome = match existing {
+        // Released between the insert and the select; let the client retry
+        None => Err(StatusCode::CONFLICT),
+        Some((stored_hash, _)) if stored_hash != request_hash => {
+            tracing::warn!("Idempotency key {} reused with a different {} request", key, scope);
+            Err(StatusCode::UNPROCESSABLE_ENTITY)
+        }
+        Some((_, None)) => {
+            tracing::info!("Idempotency key {} ({}) is still in progress", key, scope);
+            Err(StatusCode::CONFLICT)
+        }
+        Some((_, Some(response))) => {
+            tracing::info!("Replaying stored {} response for idempotency key {}", scope, key);
+            serde_json::from_value(response).map_err(|e| {
+                tracing::error!("Stored response for idempotency key {} is unreadable: {}", key, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })
+        }
+    };
+    Ok(Some(outcome))
+}
+
+async fn store_response(pool: &PgPool, scope: &str, key: &str, body: &serde_json::Value) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE idempotency_keys SET response = $3, completed_at = NOW()
+         WHERE scope = $1 AND key = $2 AND response IS NULL",
+    )
+    .bind(scope)
+    .bind(key)
+    .bind(body)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn release_key(pool: &PgPool, scope: &str, key: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM idempotency_keys WHERE scope = $1 AND key = $2 AND response IS NULL")
+        .bind(scope)
+        .bind(key)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_fingerprint_depends_on_scope_and_request() {
+        let request = serde_json::json!({ "revenue_usdc": "100" });
+        let a = fingerprint("dividends.distribute", &("inv-1", &request)).unwrap();
+
+        assert_eq!(a, fingerprint("dividends.distribute", &("inv-1", &request)).unwrap());
+        assert_eq!(a.len(), 64);
+        assert_ne!(a, fingerprint("investments.verify", &("inv-1", &request)).unwrap());
+        assert_ne!(a, fingerprint("dividends.distribute", &("inv-2", &request)).unwrap());
+        let other = serde_json::json!({ "revenue_usdc": "101" });
+        assert_ne!(a, fingerprint("dividends.distribute", &("inv-1", &other)).unwrap());
+    }
+
+    #[test]
+    fn test_key_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(idempotency_key_from_headers(&headers), Ok(None));
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static(" payout-2025-01 "));
+        assert_eq!(idempotency_key_from_headers(&headers), Ok(Some("payout-2025-01".to_string())));
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static(""));
+        assert_eq!(idempotency_key_from_headers(&headers), Err(StatusCode::BAD_REQUEST));
+
+        let long = "k".repeat(MAX_KEY_LEN + 1);
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_str(&long).unwrap());
+        assert_eq!(idempotency_key_from_headers(&headers), Err(StatusCode::BAD_REQUEST));
+    }
+}
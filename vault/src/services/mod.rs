@@ -1,4 +1,12 @@
 pub mod chain_watcher;
+pub mod claim_expiry;
+pub mod distribution_lifecycle;
+pub mod distribution_publisher;
+pub mod distribution_store;
+pub mod fee_splits;
+pub mod holder_snapshot;
+pub mod idempotency;
 pub mod pubsub;
 pub mod token_calculator;
+pub mod token_indexer;
 pub mod transaction_verifier;
@@ -3,6 +3,7 @@
 //! Calculates how many Royalty Tokens an investor receives for their USDC investment.
 //! Also handles dividend distribution math.
 
+use ethers::types::U256;
 use rust_decimal::Decimal;
 
 /// Calculate the number of royalty tokens for a given investment.
@@ -45,6 +46,102 @@ pub fn calculate_dividend_share(
     (ownership_fraction * revenue_usdc).round_dp(6)
 }
 
+/// An integer amount split by weight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Allocation {
+    /// One amount per weight, in input order. Always sums exactly to the total.
+    pub amounts: Vec<u128>,
+    /// Units left over after flooring every share, handed out one each to `dust_recipients`.
+    pub dust: u128,
+    /// Indices of the weights that received a unit of dust.
+    pub dust_recipients: Vec<usize>,
+}
+
+/// Split `total` base units in proportion to `weights` with the largest-remainder method.
+///
+/// Every weight gets the floor of its exact share; the remaining units go one each to
+/// the weights with the largest fractional remainders (ties broken by input order).
+/// No amount differs from its exact share by a full unit.
+///
+/// Returns `None` if a weight is negative, all weights are zero, or the arithmetic
+/// would overflow 256 bits.
+pub fn allocate_largest_remainder(total: u128, weights: &[Decimal]) -> Option<Allocation> {
+    if weights.iter().any(|w| w.is_sign_negative() && !w.is_zero()) {
+        return None;
+    }
+
+    // Scale every weight to the same number of decimals so the shares are exact integers
+    let scale = weights.iter().map(|w| w.normalize().scale()).max().unwrap_or(0);
+    let scaled = weights
+        .iter()
+        .map(|w| {
+            let w = w.normalize();
+            let mantissa = U256::from(w.mantissa().unsigned_abs());
+            mantissa.checked_mul(U256::exp10((scale - w.scale()) as usize))
+        })
+        .collect::<Option<Vec<U256>>>()?;
+
+    let weight_sum = scaled
+        .iter()
+        .try_fold(U256::zero(), |acc, w| acc.checked_add(*w))?;
+    if weight_sum.is_zero() {
+        return None;
+    }
+
+    let total_u256 = U256::from(total);
+    let mut amounts = Vec::with_capacity(scaled.len());
+    let mut remainders = Vec::with_capacity(scaled.len());
+    for w in &scaled {
+        let product = total_u256.checked_mul(*w)?;
+        amounts.push((product / weight_sum).as_u128());
+        remainders.push(product % weight_sum);
+    }
+
+    let dust = total - amounts.iter().sum::<u128>();
+
+    let mut order: Vec<usize> = (0..scaled.len()).collect();
+    order.sort_by(|&a, &b| remainders[b].cmp(&remainders[a]).then(a.cmp(&b)));
+
+    let mut dust_recipients: Vec<usize> = order.into_iter().take(dust as usize).collect();
+    dust_recipients.sort_unstable();
+    for &i in &dust_recipients {
+        amounts[i] += 1;
+    }
+
+    Some(Allocation {
+        amounts,
+        dust,
+        dust_recipients,
+    })
+}
+
+/// What a minimum payout does with one wallet's amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Payout {
+    /// Paid now: this distribution's amount plus the whole carry-forward balance it releases.
+    Pay { amount: u128, released: u128 },
+    /// Below the minimum: credited to the wallet's carry-forward balance.
+    CarryForward { amount: u128 },
+}
+
+/// Apply a `minimum` payout to a wallet's `amount` from this distribution and its
+/// carry-forward `balance` from earlier ones, all in base units.
+///
+/// The wallet is paid once the two together reach the minimum, so a balance that has
+/// reached it is paid even when the wallet gains nothing this time.
+/// Returns `None` if the sum would overflow.
+pub fn apply_minimum_payout(amount: u128, balance: u128, minimum: u128) -> Option<Payout> {
+    let total = amount.checked_add(balance)?;
+    if total >= minimum && total > 0 {
+        Some(Payout::Pay {
+            amount: total,
+            released: balance,
+        })
+    } else {
+        Some(Payout::CarryForward { amount })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,4 +188,104 @@ mod tests {
         );
         assert_eq!(share, Decimal::from(50)); // $50
     }
+
+    #[test]
+    fn test_allocation_hands_dust_to_largest_remainders() {
+        // 100 / 3 = 33.33 each; one unit of dust goes to the first holder on the tie
+        let allocation = allocate_largest_remainder(100, &[Decimal::ONE, Decimal::ONE, Decimal::ONE]).unwrap();
+        assert_eq!(allocation.amounts, vec![34, 33, 33]);
+        assert_eq!(allocation.dust, 1);
+        assert_eq!(allocation.dust_recipients, vec![0]);
+
+        // 10 * 0.15 = 1.5, 10 * 0.25 = 2.5, 10 * 0.6 = 6
+        let weights = [Decimal::new(15, 2), Decimal::new(25, 2), Decimal::new(6, 1)];
+        let allocation = allocate_largest_remainder(10, &weights).unwrap();
+        assert_eq!(allocation.amounts, vec![2, 2, 6]);
+        assert_eq!(allocation.dust_recipients, vec![0]);
+    }
+
+    #[test]
+    fn test_allocation_rejects_degenerate_weights() {
+        assert!(allocate_largest_remainder(100, &[]).is_none());
+        assert!(allocate_largest_remainder(100, &[Decimal::ZERO]).is_none());
+        assert!(allocate_largest_remainder(100, &[Decimal::ONE, Decimal::NEGATIVE_ONE]).is_none());
+    }
+
+    #[test]
+    fn test_minimum_payout_carries_small_amounts_forward() {
+        // $0.004 against a $0.01 minimum
+        assert_eq!(apply_minimum_payout(4_000, 0, 10_000), Some(Payout::CarryForward { amount: 4_000 }));
+        // Later rounds of $0.002 each until the balance reaches the minimum
+        assert_eq!(apply_minimum_payout(2_000, 6_000, 10_000), Some(Payout::CarryForward { amount: 2_000 }));
+        assert_eq!(
+            apply_minimum_payout(2_000, 8_000, 10_000),
+            Some(Payout::Pay { amount: 10_000, released: 8_000 })
+        );
+    }
+
+    #[test]
+    fn test_minimum_payout_pays_large_amounts_and_balances() {
+        assert_eq!(apply_minimum_payout(50_000, 0, 10_000), Some(Payout::Pay { amount: 50_000, released: 0 }));
+        // A balance that reaches the minimum is paid without a new amount
+        assert_eq!(apply_minimum_payout(0, 12_000, 10_000), Some(Payout::Pay { amount: 12_000, released: 12_000 }));
+        // Nothing at all is never a leaf
+        assert_eq!(apply_minimum_payout(0, 0, 0), Some(Payout::CarryForward { amount: 0 }));
+        assert_eq!(apply_minimum_payout(u128::MAX, 1, 10_000), None);
+    }
+
+    /// xorshift64: small deterministic PRNG, so failures reproduce from the seed
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_allocation_properties_over_random_holder_sets() {
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+
+        for _ in 0..500 {
+            let holders = 1 + (rng.next() % 200) as usize;
+            // Up to 1B USDC in base units
+            let total = (rng.next() % 1_000_000_000_000_000) as u128;
+            // 18-decimal token balances, including zero and dust-sized ones
+            let weights: Vec<Decimal> = (0..holders)
+                .map(|_| match rng.next() % 10 {
+                    0 => Decimal::ZERO,
+                    1 => Decimal::new((rng.next() % 1000) as i64, 18),
+                    _ => Decimal::new((rng.next() % 1_000_000_000_000_000) as i64, 6),
+                })
+                .collect();
+            if weights.iter().all(|w| w.is_zero()) {
+                continue;
+            }
+
+            let allocation = allocate_largest_remainder(total, &weights).unwrap();
+            let weight_sum: Decimal = weights.iter().sum();
+
+            assert_eq!(allocation.amounts.len(), holders);
+            assert_eq!(allocation.amounts.iter().sum::<u128>(), total);
+            assert!(allocation.dust < holders as u128);
+            assert_eq!(allocation.dust_recipients.len() as u128, allocation.dust);
+
+            for (i, (amount, weight)) in allocation.amounts.iter().zip(&weights).enumerate() {
+                if weight.is_zero() {
+                    assert_eq!(*amount, 0);
+                    assert!(!allocation.dust_recipients.contains(&i));
+                }
+                // Within one unit of the exact share
+                let exact = Decimal::from(total) * *weight / weight_sum;
+                let diff = (Decimal::from(*amount) - exact).abs();
+                assert!(diff < Decimal::ONE, "holder {} got {} for exact share {}", i, amount, exact);
+            }
+
+            // Deterministic for the same input
+            assert_eq!(allocate_largest_remainder(total, &weights).unwrap(), allocation);
+        }
+    }
 }
@@ -0,0 +1,517 @@
+//! RoyaltyToken Transfer Indexer
+//!
+//! Ingests ERC-20 `Transfer` events for every `invention_ledger.royalty_token_address`
+//! into `token_transfers`, and folds them into `token_balance_history` and
+//! `token_holder_counts`. Each token has a checkpoint in `token_index_checkpoints`;
+//! a batch of logs and its checkpoint are committed together, so a crash never
+//! double-counts or skips a transfer.
+//!
+//! Only blocks `TOKEN_INDEX_CONFIRMATIONS` behind the head are indexed, so indexed
+//! history is not rewritten by ordinary reorgs.
+
+use anyhow::{anyhow, Result};
+use ethers::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
+use crate::services::holder_snapshot::{apply_transfer, decode_transfer_log};
+
+/// How often royalty tokens are polled for new `Transfer` events.
+const TOKEN_INDEX_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Blocks behind the chain head that are considered final enough to index.
+pub(crate) const TOKEN_INDEX_CONFIRMATIONS: u64 = 12;
+
+/// Blocks requested per `eth_getLogs` call; most RPC providers cap the range.
+const TRANSFER_LOG_CHUNK: u64 = 10_000;
+
+/// A decoded `Transfer` event with its position on chain.
+#[derive(Debug, Clone)]
+struct IndexedTransfer {
+    block_number: u64,
+    log_index: u64,
+    tx_hash: String,
+    from: Address,
+    to: Address,
+    value: U256,
+}
+
+/// Balance history and holder counts produced by folding a batch of transfers.
+#[derive(Debug, Default, PartialEq)]
+struct FoldedTransfers {
+    /// (wallet, block, balance at the end of that block)
+    balances: Vec<(Address, u64, U256)>,
+    /// (block, holders with a non-zero balance at the end of that block)
+    holder_counts: Vec<(u64, i64)>,
+}
+
+/// Index `Transfer` events of every royalty token in `invention_ledger`.
+///
+/// Tokens are re-read from `invention_ledger` on every poll. A token seen for the
+/// first time is indexed from genesis.
+pub async fn index_royalty_tokens(rpc_url: &str, pool: sqlx::PgPool) -> Result<()> {
+    let provider = Provider::<Http>::try_from(rpc_url)?;
+    let mut interval = tokio::time::interval(TOKEN_INDEX_POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let tokens: Vec<String> = match sqlx::query_scalar(
+            "SELECT DISTINCT LOWER(royalty_token_address) FROM invention_ledger WHERE royalty_token_address IS NOT NULL",
+        )
+        .fetch_all(&pool)
+        .await
+        {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                tracing::warn!("Failed to load royalty tokens: {}", e);
+                continue;
+            }
+        };
+        if tokens.is_empty() {
+            continue;
+        }
+
+        let head = match provider.get_block_number().await {
+            Ok(head) => head.as_u64(),
+            Err(e) => {
+                tracing::warn!("Failed to fetch block number: {}", e);
+                continue;
+            }
+        };
+        let Some(safe_head) = head.checked_sub(TOKEN_INDEX_CONFIRMATIONS) else {
+            continue;
+        };
+
+        for token in tokens {
+            let token: Address = match token.parse() {
+                Ok(token) => token,
+                Err(e) => {
+                    tracing::warn!("Invalid royalty_token_address {}: {}", token, e);
+                    continue;
+                }
+            };
+
+            // Progress is checkpointed per batch; a failed token resumes on the next poll
+            if let Err(e) = index_token(&provider, &pool, token, safe_head).await {
+                tracing::error!("Failed to index transfers of {:?}: {}", token, e);
+            }
+        }
+    }
+}
+
+/// Index `token` from its checkpoint up to `safe_head`, one log chunk per transaction.
+async fn index_token(
+    provider: &Provider<Http>,
+    pool: &sqlx::PgPool,
+    token: Address,
+    safe_head: u64,
+) -> Result<()> {
+    let token_key = format!("{:#x}", token);
+    let mut from = match indexed_through(pool, &token_key).await? {
+        Some(block) => block + 1,
+        None => 0,
+    };
+
+    while from <= safe_head {
+        let to = (from + TRANSFER_LOG_CHUNK - 1).min(safe_head);
+        let filter = Filter::new()
+            .address(token)
+            .event("Transfer(address,address,uint256)")
+            .from_block(from)
+            .to_block(to);
+
+        let logs = provider
+            .get_logs(&filter)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch Transfer logs {}..{}: {}", from, to, e))?;
+
+        let transfers = logs.iter().map(decode_indexed_transfer).collect::<Result<Vec<_>>>()?;
+        ingest_transfers(pool, &token_key, transfers, to).await?;
+        from = to + 1;
+    }
+
+    Ok(())
+}
+
+fn decode_indexed_transfer(log: &Log) -> Result<IndexedTransfer> {
+    let (from, to, value) = decode_transfer_log(log)?;
+
+    Ok(IndexedTransfer {
+        block_number: log
+            .block_number
+            .ok_or_else(|| anyhow!("Transfer log without block number"))?
+            .as_u64(),
+        log_index: log
+            .log_index
+            .ok_or_else(|| anyhow!("Transfer log without log index"))?
+            .as_u64(),
+        tx_hash: log
+            .transaction_hash
+            .map(|h| format!("{:?}", h))
+            .ok_or_else(|| anyhow!("Transfer log without transaction hash"))?,
+        from,
+        to,
+        value,
+    })
+}
+
+/// Store one chunk of transfers and advance the checkpoint to `through_block`.
+async fn ingest_transfers(
+    pool: &sqlx::PgPool,
+    token: &str,
+    mut transfers: Vec<IndexedTransfer>,
+    through_block: u64,
+) -> Result<()> {
+    transfers.sort_by_key(|t| (t.block_number, t.log_index));
+
+    let mut tx = pool.begin().await?;
+
+    if !transfers.is_empty() {
+        // Start from each touched wallet's latest indexed balance
+        let wallets: Vec<String> = transfers
+            .iter()
+            .flat_map(|t| [t.from, t.to])
+            .filter(|a| !a.is_zero())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|a| format!("{:#x}", a))
+            .collect();
+
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT DISTINCT ON (wallet_address) wallet_address, balance::TEXT
+             FROM token_balance_history
+             WHERE token_address = $1 AND wallet_address = ANY($2)
+             ORDER BY wallet_address, block_number DESC",
+        )
+        .bind(token)
+        .bind(&wallets)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut balances = HashMap::new();
+        for (wallet, balance) in rows {
+            balances.insert(wallet.parse::<Address>()?, U256::from_dec_str(&balance)?);
+        }
+
+        let holder_count: i64 = sqlx::query_scalar(
+            "SELECT holder_count FROM token_holder_counts WHERE token_address = $1 ORDER BY block_number DESC LIMIT 1",
+        )
+        .bind(token)
+        .fetch_optional(&mut *tx)
+        .await?
+        .unwrap_or(0);
+
+        let folded = fold_transfers(&mut balances, holder_count, &transfers)?;
+
+        insert_transfers(&mut tx, token, &transfers).await?;
+        insert_folded(&mut tx, token, &folded).await?;
+    }
+
+    sqlx::query(
+        "INSERT INTO token_index_checkpoints (token_address, last_indexed_block, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (token_address) DO UPDATE
+         SET last_indexed_block = EXCLUDED.last_indexed_block, updated_at = NOW()",
+    )
+    .bind(token)
+    .bind(through_block as i64)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    if !transfers.is_empty() {
+        tracing::info!(
+            "Indexed {} transfers of {} through block {}",
+            transfers.len(),
+            token,
+            through_block
+        );
+    }
+    Ok(())
+}
+
+/// Insert a chunk of transfers with a single `UNNEST` statement.
+async fn insert_transfers(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    token: &str,
+    transfers: &[IndexedTransfer],
+) -> Result<()> {
+    let block_numbers: Vec<i64> = transfers.iter().map(|t| t.block_number as i64).collect();
+    let log_indexes: Vec<i64> = transfers.iter().map(|t| t.log_index as i64).collect();
+    let tx_hashes: Vec<&str> = transfers.iter().map(|t| t.tx_hash.as_str()).collect();
+    let from_addresses: Vec<String> = transfers.iter().map(|t| format!("{:#x}", t.from)).collect();
+    let to_addresses: Vec<String> = transfers.iter().map(|t| format!("{:#x}", t.to)).collect();
+    // U256 values exceed every integer type Postgres binds, so they travel as text
+    let values: Vec<String> = transfers.iter().map(|t| t.value.to_string()).collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO token_transfers (token_address, block_number, log_index, tx_hash, from_address, to_address, value)
+        SELECT $1, t.block_number, t.log_index, t.tx_hash, t.from_address, t.to_address, t.value::NUMERIC
+        FROM UNNEST($2::int8[], $3::int8[], $4::text[], $5::text[], $6::text[], $7::text[])
+             AS t(block_number, log_index, tx_hash, from_address, to_address, value)
+        "#,
+    )
+    .bind(token)
+    .bind(&block_numbers)
+    .bind(&log_indexes)
+    .bind(&tx_hashes)
+    .bind(&from_addresses)
+    .bind(&to_addresses)
+    .bind(&values)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Insert the balance history and holder counts folded from a chunk, one `UNNEST`
+/// statement per table.
+async fn insert_folded(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    token: &str,
+    folded: &FoldedTransfers,
+) -> Result<()> {
+    let wallets: Vec<String> = folded.balances.iter().map(|(w, _, _)| format!("{:#x}", w)).collect();
+    let balance_blocks: Vec<i64> = folded.balances.iter().map(|(_, b, _)| *b as i64).collect();
+    let balances: Vec<String> = folded.balances.iter().map(|(_, _, v)| v.to_string()).collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO token_balance_history (token_address, wallet_address, block_number, balance)
+        SELECT $1, b.wallet_address, b.block_number, b.balance::NUMERIC
+        FROM UNNEST($2::text[], $3::int8[], $4::text[]) AS b(wallet_address, block_number, balance)
+        "#,
+    )
+    .bind(token)
+    .bind(&wallets)
+    .bind(&balance_blocks)
+    .bind(&balances)
+    .execute(&mut **tx)
+    .await?;
+
+    let count_blocks: Vec<i64> = folded.holder_counts.iter().map(|(b, _)| *b as i64).collect();
+    let counts: Vec<i64> = folded.holder_counts.iter().map(|(_, c)| *c).collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO token_holder_counts (token_address, block_number, holder_count)
+        SELECT $1, c.block_number, c.holder_count
+        FROM UNNEST($2::int8[], $3::int8[]) AS c(block_number, holder_count)
+        "#,
+    )
+    .bind(token)
+    .bind(&count_blocks)
+    .bind(&counts)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Apply `transfers` (sorted by block and log index) to `balances`, recording each
+/// touched wallet's end-of-block balance and the holder count after every block.
+fn fold_transfers(
+    balances: &mut HashMap<Address, U256>,
+    mut holder_count: i64,
+    transfers: &[IndexedTransfer],
+) -> Result<FoldedTransfers> {
+    let mut folded = FoldedTransfers::default();
+
+    for block in transfers.chunk_by(|a, b| a.block_number == b.block_number) {
+        let block_number = block[0].block_number;
+
+        // Balance of each touched wallet before this block
+        let mut before: BTreeMap<Address, U256> = BTreeMap::new();
+        for t in block {
+            for wallet in [t.from, t.to] {
+                if !wallet.is_zero() {
+                    before
+                        .entry(wallet)
+                        .or_insert_with(|| balances.get(&wallet).copied().unwrap_or_default());
+                }
+            }
+            apply_transfer(balances, t.from, t.to, t.value)?;
+        }
+
+        for (wallet, previous) in before {
+            let balance = balances.get(&wallet).copied().unwrap_or_default();
+            holder_count += i64::from(!balance.is_zero()) - i64::from(!previous.is_zero());
+            folded.balances.push((wallet, block_number, balance));
+        }
+        folded.holder_counts.push((block_number, holder_count));
+    }
+
+    Ok(folded)
+}
+
+/// Last block indexed for `token` (lowercase address), or `None` if it has never been indexed.
+pub async fn indexed_through(pool: &sqlx::PgPool, token: &str) -> Result<Option<u64>> {
+    let block: Option<i64> = sqlx::query_scalar(
+        "SELECT last_indexed_block FROM token_index_checkpoints WHERE token_address = $1",
+    )
+    .bind(token)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(block.map(|b| b as u64))
+}
+
+/// Non-zero balances of every holder of `token` at the end of `block`, sorted by wallet.
+///
+/// Returns `None` if the index has not reached `block` yet.
+pub async fn balances_at_block(
+    pool: &sqlx::PgPool,
+    token: &str,
+    block: u64,
+) -> Result<Option<Vec<(Address, U256)>>> {
+    match indexed_through(pool, token).await? {
+        Some(indexed) if indexed >= block => {}
+        _ => return Ok(None),
+    }
+
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT wallet_address, balance::TEXT FROM (
+             SELECT DISTINCT ON (wallet_address) wallet_address, balance
+             FROM token_balance_history
+             WHERE token_address = $1 AND block_number <= $2
+             ORDER BY wallet_address, block_number DESC
+         ) latest
+         WHERE balance > 0
+         ORDER BY wallet_address",
+    )
+    .bind(token)
+    .bind(block as i64)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|(wallet, balance)| Ok((wallet.parse()?, U256::from_dec_str(&balance)?)))
+        .collect::<Result<Vec<_>>>()
+        .map(Some)
+}
+
+/// Holder count of `token` after every block between `from_block` and `to_block` that
+/// changed balances, starting with the count in effect at `from_block`.
+pub async fn holder_counts(
+    pool: &sqlx::PgPool,
+    token: &str,
+    from_block: u64,
+    to_block: u64,
+) -> Result<Vec<(u64, i64)>> {
+    let rows: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT block_number, holder_count FROM token_holder_counts
+         WHERE token_address = $1
+           AND block_number <= $3
+           AND block_number >= COALESCE(
+               (SELECT MAX(block_number) FROM token_holder_counts WHERE token_address = $1 AND block_number <= $2),
+               $2)
+         ORDER BY block_number",
+    )
+    .bind(token)
+    .bind(from_block as i64)
+    .bind(to_block as i64)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|(block, count)| (block as u64, count)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn transfer(block_number: u64, log_index: u64, from: Address, to: Address, value: u64) -> IndexedTransfer {
+        IndexedTransfer {
+            block_number,
+            log_index,
+            tx_hash: format!("0x{:064x}", block_number * 1000 + log_index),
+            from,
+            to,
+            value: U256::from(value),
+        }
+    }
+
+    #[test]
+    fn test_fold_records_end_of_block_balances_and_holder_counts() {
+        let transfers = vec![
+            transfer(10, 0, Address::zero(), addr(1), 100),
+            transfer(10, 1, addr(1), addr(2), 40),
+            transfer(20, 0, addr(2), addr(3), 40),
+            transfer(30, 0, addr(1), Address::zero(), 60),
+        ];
+
+        let mut balances = HashMap::new();
+        let folded = fold_transfers(&mut balances, 0, &transfers).unwrap();
+
+        assert_eq!(
+            folded.balances,
+            vec![
+                (addr(1), 10, U256::from(60)),
+                (addr(2), 10, U256::from(40)),
+                (addr(2), 20, U256::zero()),
+                (addr(3), 20, U256::from(40)),
+                (addr(1), 30, U256::zero()),
+            ]
+        );
+        assert_eq!(folded.holder_counts, vec![(10, 2), (20, 2), (30, 1)]);
+    }
+
+    #[test]
+    fn test_fold_continues_from_existing_state() {
+        let mut balances = HashMap::from([(addr(1), U256::from(5))]);
+        let transfers = vec![transfer(7, 0, addr(1), addr(2), 5)];
+
+        let folded = fold_transfers(&mut balances, 1, &transfers).unwrap();
+        assert_eq!(folded.holder_counts, vec![(7, 1)]);
+        assert_eq!(balances[&addr(2)], U256::from(5));
+    }
+
+    #[test]
+    fn test_fold_rejects_transfer_from_unknown_balance() {
+        let mut balances = HashMap::new();
+        let transfers = vec![transfer(1, 0, addr(1), addr(2), 1)];
+        assert!(fold_transfers(&mut balances, 0, &transfers).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs VAULT_TEST_DATABASE_URL"]
+    async fn test_ingest_transfers_stores_chunks_in_bulk() {
+        let url = std::env::var("VAULT_TEST_DATABASE_URL").expect("VAULT_TEST_DATABASE_URL must be set");
+        let pool = sqlx::PgPool::connect(&url).await.expect("connect to VAULT_TEST_DATABASE_URL");
+        sqlx::migrate!("./migrations").run(&pool).await.expect("run migrations");
+        let token = format!("{:#x}", Address::random());
+
+        // Values above u128 must survive the round trip
+        let large = U256::from(u128::MAX) * 4;
+        let mut mint = transfer(10, 0, Address::zero(), addr(1), 0);
+        mint.value = large;
+        let chunk = vec![transfer(10, 1, addr(1), addr(2), 40), mint];
+        ingest_transfers(&pool, &token, chunk, 15).await.unwrap();
+        ingest_transfers(&pool, &token, vec![transfer(20, 0, addr(2), addr(3), 40)], 25).await.unwrap();
+
+        assert_eq!(indexed_through(&pool, &token).await.unwrap(), Some(25));
+        assert_eq!(
+            balances_at_block(&pool, &token, 10).await.unwrap().unwrap(),
+            vec![(addr(1), large - 40), (addr(2), U256::from(40))]
+        );
+        assert_eq!(
+            balances_at_block(&pool, &token, 20).await.unwrap().unwrap(),
+            vec![(addr(1), large - 40), (addr(3), U256::from(40))]
+        );
+        assert_eq!(holder_counts(&pool, &token, 0, 25).await.unwrap(), vec![(10, 2), (20, 2)]);
+
+        let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM token_transfers WHERE token_address = $1")
+            .bind(&token)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, 3);
+    }
+}